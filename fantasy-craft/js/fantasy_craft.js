@@ -0,0 +1,16 @@
+// miniquad plugin providing the JavaScript functions imported by fantasy-craft
+// when the `web-focus` feature is enabled. Load it after mq_js_bundle.js and before load("game.wasm"):
+//
+//     <script src="mq_js_bundle.js"></script>
+//     <script src="fantasy_craft.js"></script>
+//     <script>load("game.wasm");</script>
+miniquad_add_plugin({
+    name: "fantasy_craft",
+    version: 1,
+    register_plugin: function (importObject) {
+        // Focus of the page, read each frame by `ScreenInfo::current`
+        importObject.env.js_has_focus = function () {
+            return document.hasFocus() ? 1 : 0;
+        };
+    }
+});
@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::ComponentLoader;
+use crate::core::error::EngineError;
+use crate::scene::scene_loader::{insert_component, parse_component, save_component};
+
+/// Sound attached to an entity, e.g. a campfire loop.
+/// It starts playing when the component is added and stops as soon as
+/// the component is removed or the entity is despawned.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AudioSource {
+    pub sound: String,
+    pub looped: bool,
+    pub volume: f32
+}
+
+impl AudioSource {
+    pub fn new(sound: &str) -> Self {
+        Self {
+            sound: sound.to_string(),
+            looped: false,
+            volume: 1.0
+        }
+    }
+}
+
+fn default_volume() -> f32 {
+    1.0
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AudioSourceLoaderData {
+    pub sound: String,
+
+    #[serde(default)]
+    pub looped: bool,
+
+    #[serde(default = "default_volume")]
+    pub volume: f32
+}
+
+pub struct AudioSourceLoader;
+
+impl ComponentLoader for AudioSourceLoader {
+    fn load(&self, ctx: &mut crate::prelude::Context, entity: hecs::Entity, data: &serde_json::Value) -> Result<(), EngineError> {
+        let loader_data: AudioSourceLoaderData = parse_component("AudioSource", data)?;
+
+        let component = AudioSource {
+            sound: loader_data.sound,
+            looped: loader_data.looped,
+            volume: loader_data.volume
+        };
+
+        insert_component(ctx, entity, component)
+    }
+
+    fn save(&self, ctx: &crate::prelude::Context, entity: hecs::Entity) -> Option<serde_json::Value> {
+        save_component(ctx, entity, |component: &AudioSource| AudioSourceLoaderData {
+            sound: component.sound.clone(),
+            looped: component.looped,
+            volume: component.volume
+        })
+    }
+}
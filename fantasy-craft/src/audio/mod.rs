@@ -1,3 +1,4 @@
+pub mod components;
 pub mod event;
 pub mod system;
 pub mod plugin;
\ No newline at end of file
@@ -1,12 +1,29 @@
-use crate::{audio::system::audio_system, prelude::{GameState, Plugin, Stage, System}};
+use crate::{audio::{components::{AudioSource, AudioSourceLoader}, system::{audio_source_added_hook, audio_source_removed_hook, audio_system, stop_all_sounds_system}}, prelude::{GameState, Plugin, Stage, System, has_window}};
+use crate::core::plugins::{CorePlugin, PluginId};
 
 pub struct AudioPlugin;
 
 impl Plugin for AudioPlugin {
     fn build(&self, app: &mut crate::prelude::App) {
-        app.add_system(Stage::PostUpdate, System::new(
+        app.scene_loader
+            .register("AudioSource", Box::new(AudioSourceLoader));
+
+        app
+            .on_add::<AudioSource>(audio_source_added_hook)
+            .on_remove::<AudioSource>(audio_source_removed_hook);
+
+        app.add_system(Stage::PostUpdate, System::with_local(
             audio_system,
             vec![GameState::Playing, GameState::Menu]
-        ));
+        ).run_if(has_window()));
+
+        app.add_system(Stage::OnShutdown, System::new(
+            stop_all_sounds_system,
+            vec![GameState::Menu, GameState::Playing, GameState::Paused, GameState::Loading]
+        ).run_if(has_window()));
+    }
+
+    fn dependencies(&self) -> Vec<PluginId> {
+        vec![PluginId::of::<CorePlugin>()]
     }
 }
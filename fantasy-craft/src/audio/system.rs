@@ -1,9 +1,13 @@
-use macroquad::audio::play_sound_once;
+use hecs::Entity;
+use macroquad::audio::{PlaySoundParams, play_sound, play_sound_once, stop_sound};
+use macroquad::logging::warn;
+use crate::audio::components::AudioSource;
 use crate::audio::event::PlaySoundEvent;
 use crate::core::context::Context;
-use crate::core::event::EventBus;
+use crate::core::event::{EventBus, EventReader};
+use crate::core::schedule::Local;
 
-pub fn audio_system(ctx: &mut Context) {
+pub fn audio_system(ctx: &mut Context, reader: &mut Local<EventReader<PlaySoundEvent>>) {
     // 1. Split borrows (ResourceMap vs AssetServer stored in Context)
     // Assuming AssetServer is a field in Context, not in ResourceMap
     // If AssetServer is in ResourceMap, use: ctx.resources.get::<AssetServer>()
@@ -15,7 +19,7 @@ pub fn audio_system(ctx: &mut Context) {
 
     if let Some(event_bus) = event_bus_opt {
         // 2. Read events
-        for event in event_bus.read::<PlaySoundEvent>() {
+        for event in reader.read(event_bus) {
             if let Some(sound) = asset_server.get_sound(&event.sound_name) {
                 // Macroquad function to play sound
                 play_sound_once(sound);
@@ -25,3 +29,37 @@ pub fn audio_system(ctx: &mut Context) {
         }
     }
 }
+
+/// `on_add` hook of `AudioSource`: starts the sound of the entity.
+pub fn audio_source_added_hook(ctx: &mut Context, entity: Entity) {
+    let Ok(source) = ctx.world.get::<&AudioSource>(entity) else {
+        return;
+    };
+
+    match ctx.asset_server.get_sound(&source.sound) {
+        Some(sound) => play_sound(sound, PlaySoundParams {
+            looped: source.looped,
+            volume: source.volume
+        }),
+        None => warn!("AudioSource: Sound not found: {}", source.sound)
+    }
+}
+
+/// `on_remove` hook of `AudioSource`: stops the sound when the entity is despawned or loses the component.
+/// macroquad stops every instance of a sound, including ones started by other entities.
+pub fn audio_source_removed_hook(ctx: &mut Context, entity: Entity) {
+    let Ok(source) = ctx.world.get::<&AudioSource>(entity) else {
+        return;
+    };
+
+    if let Some(sound) = ctx.asset_server.get_sound(&source.sound) {
+        stop_sound(sound);
+    }
+}
+
+/// Stops every playing sound when the app shuts down.
+pub fn stop_all_sounds_system(ctx: &mut Context) {
+    for sound in ctx.asset_server.sounds() {
+        stop_sound(sound);
+    }
+}
@@ -1,9 +1,12 @@
 use hecs::Entity;
 use macroquad::prelude::*;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use crate::core::context::Context;
+use crate::scene::entity_ref::{EntityRef, resolve_entity_ref};
 use crate::scene::scene_loader::ComponentLoader;
+use crate::core::error::EngineError;
+use crate::scene::scene_loader::{insert_component, parse_component, save_component, save_marker};
 
 #[derive(Debug)]
 pub struct CameraComponent {
@@ -15,7 +18,7 @@ pub struct CameraComponent {
 fn default_lerp() -> f32 { 0.0 }
 fn default_zoom() -> f32 { 1.0 }
 
-#[derive(Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 struct CameraComponentLoaderData {
     #[serde(default="default_lerp")]
     pub lerp_factor: f32,
@@ -27,9 +30,8 @@ struct CameraComponentLoaderData {
 pub struct CameraComponentLoader;
 
 impl ComponentLoader for CameraComponentLoader {
-    fn load(&self, ctx: &mut Context, entity: Entity, data: &Value) {
-        let loader_data: CameraComponentLoaderData = serde_json::from_value(data.clone())
-            .unwrap_or_default();
+    fn load(&self, ctx: &mut Context, entity: Entity, data: &Value) -> Result<(), EngineError> {
+        let loader_data: CameraComponentLoaderData = parse_component("CameraComponent", data)?;
 
         let component = CameraComponent {
             lerp_factor: loader_data.lerp_factor,
@@ -37,7 +39,14 @@ impl ComponentLoader for CameraComponentLoader {
             camera: Camera2D::default()
         };
 
-        ctx.world.insert_one(entity, component).expect("Failed to insert CameraComponent");
+        insert_component(ctx, entity, component)
+    }
+
+    fn save(&self, ctx: &Context, entity: Entity) -> Option<Value> {
+        save_component(ctx, entity, |component: &CameraComponent| CameraComponentLoaderData {
+            lerp_factor: component.lerp_factor,
+            zoom: component.zoom
+        })
     }
 }
 
@@ -47,8 +56,36 @@ pub struct MainCamera;
 pub struct MainCameraLoader;
 
 impl ComponentLoader for MainCameraLoader {
-    fn load(&self, ctx: &mut Context, entity: Entity, _data: &Value) {
-        ctx.world.insert_one(entity, MainCamera).expect("Failed to insert MainCamera");
+    fn load(&self, ctx: &mut Context, entity: Entity, _data: &Value) -> Result<(), EngineError> {
+        insert_component(ctx, entity, MainCamera)
+    }
+
+    fn save(&self, ctx: &Context, entity: Entity) -> Option<Value> {
+        save_marker::<MainCamera>(ctx, entity)
+    }
+}
+
+/// Entity followed by this camera, written `"@id"` in the scene. Cameras without it follow the `CameraTarget`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CameraFollow(pub Entity);
+
+pub struct CameraFollowLoader;
+
+impl ComponentLoader for CameraFollowLoader {
+    fn load(&self, ctx: &mut Context, entity: Entity, data: &Value) -> Result<(), EngineError> {
+        let target: EntityRef = parse_component("CameraFollow", data)?;
+
+        resolve_entity_ref(ctx, entity, target, |ctx, entity, target| {
+            insert_component(ctx, entity, CameraFollow(target))
+        });
+
+        Ok(())
+    }
+
+    fn save(&self, ctx: &Context, entity: Entity) -> Option<Value> {
+        let target = ctx.world.get::<&CameraFollow>(entity).ok()?.0;
+
+        EntityRef::of(ctx, target).map(|target| Value::String(target.to_string()))
     }
 }
 
@@ -58,7 +95,11 @@ pub struct CameraTarget;
 pub struct CameraTargetLoader;
 
 impl ComponentLoader for CameraTargetLoader {
-    fn load(&self, ctx: &mut Context, entity: Entity, _data: &Value) {
-        ctx.world.insert_one(entity, CameraTarget).expect("Failed to insert CameraTarget");
+    fn load(&self, ctx: &mut Context, entity: Entity, _data: &Value) -> Result<(), EngineError> {
+        insert_component(ctx, entity, CameraTarget)
+    }
+
+    fn save(&self, ctx: &Context, entity: Entity) -> Option<Value> {
+        save_marker::<CameraTarget>(ctx, entity)
     }
 }
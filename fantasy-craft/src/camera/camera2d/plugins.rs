@@ -1,4 +1,6 @@
-use crate::{core::plugins::Plugin, prelude::{CameraComponentLoader, CameraTargetLoader, GameState, MainCameraLoader, Stage, System, update_camera}};
+use crate::{core::plugins::Plugin, prelude::{CameraComponentLoader, CameraFollowLoader, CameraTargetLoader, GameState, MainCameraLoader, Stage, System, update_camera}};
+use crate::hierarchy::plugins::HierarchyPlugin;
+use crate::core::plugins::PluginId;
 
 pub struct Camera2dPlugin;
 
@@ -7,12 +9,17 @@ impl Plugin for Camera2dPlugin {
         app.scene_loader
             .register("CameraComponent", Box::new(CameraComponentLoader))
             .register("MainCamera", Box::new(MainCameraLoader))
-            .register("CameraTarget", Box::new(CameraTargetLoader));
+            .register("CameraTarget", Box::new(CameraTargetLoader))
+            .register("CameraFollow", Box::new(CameraFollowLoader));
 
         app
             .add_system(Stage::PostUpdate, System::new(
                 update_camera,
                 vec![GameState::Playing]
-            ));
+            ).label("camera_update").after("hierarchy_transform"));
+    }
+
+    fn dependencies(&self) -> Vec<PluginId> {
+        vec![PluginId::of::<HierarchyPlugin>()]
     }
 }
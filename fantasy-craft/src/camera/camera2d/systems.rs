@@ -1,14 +1,22 @@
 use macroquad::prelude::*;
-use crate::{physics::components::Transform, core::context::Context, graphics::tiled_map::components::{MainTileMap, TileMapComponent}};
-use crate::camera::camera2d::components::{CameraTarget, CameraComponent, MainCamera};
+use crate::{physics::{components::Transform, helpers::interpolated_position}, core::{app::Headless, context::Context, time::FixedTime}, graphics::tiled_map::components::{MainTileMap, TileMapComponent}};
+use crate::camera::camera2d::components::{CameraFollow, CameraTarget, CameraComponent, MainCamera};
 
 pub fn update_camera(ctx: &mut Context) {
-    // 1. Trouver la cible (inchangé)
-    let mut target_position: Option<Vec2> = None;
-    for (_entity, (transform, _)) in ctx.world.query::<(&Transform, &CameraTarget)>().iter() {
-        target_position = Some(transform.position);
-        break;
-    }
+    // 1. Trouver la cible : l'entité suivie par la caméra principale, sinon la première CameraTarget
+    let followed = ctx.world.query::<(&CameraFollow, &MainCamera)>()
+        .iter()
+        .next()
+        .map(|(_entity, (follow, _))| follow.0);
+
+    // Position interpolée entre les deux derniers pas fixes, comme pour le rendu des sprites
+    let alpha = ctx.get_resource::<FixedTime>().map_or(1.0, |fixed_time| fixed_time.alpha());
+
+    let target = followed.or_else(|| ctx.world.query::<(&Transform, &CameraTarget)>()
+        .iter()
+        .next()
+        .map(|(entity, _)| entity));
+    let target_position = target.and_then(|target| interpolated_position(ctx, target, alpha));
 
     let Some(target_position) = target_position else {
         return;
@@ -27,37 +35,89 @@ pub fn update_camera(ctx: &mut Context) {
     }
 
     // 3. Mettre à jour la caméra
-    for (_entity, (camera_comp, transform, _main)) in ctx.world.query::<(&mut CameraComponent, &mut Transform, &MainCamera)>().iter() {
+    let screen = ctx.screen();
+    let has_window = ctx.get_resource::<Headless>().is_none();
+    let mut moved = Vec::new();
+
+    for (entity, (camera_comp, transform, _main)) in ctx.world.query::<(&mut CameraComponent, &mut Transform, &MainCamera)>().iter() {
         let lerp_speed = 1.0 - (-camera_comp.lerp_factor * ctx.dt()).exp();
 
         let mut desired_position = transform.position.lerp(target_position, lerp_speed);
 
         if let Some(world_size) = world_size {
-            // Note: screen_width() / zoom donne la taille de la vue en unités monde
+            // Note: screen.width / zoom donne la taille de la vue en unités monde
             // Attention : Macroquad gère le zoom bizarrement (1.0 = 1 pixel écran par unité monde si zoom non normalisé)
             // Ta formule de zoom ci-dessous normalise par rapport à l'écran, ce qui est bien.
             
             // Calcul approximatif de la demi-vue pour le clamping
             // (Peut nécessiter d'être ajusté selon si ta caméra est centrée ou non)
-            let half_view_w = (screen_width() / camera_comp.zoom) * 0.5;
-            let half_view_h = (screen_height() / camera_comp.zoom) * 0.5;
+            let half_view_w = (screen.width / camera_comp.zoom) * 0.5;
+            let half_view_h = (screen.height / camera_comp.zoom) * 0.5;
 
             desired_position.x = desired_position.x.clamp(half_view_w, world_size.x - half_view_w);
             desired_position.y = desired_position.y.clamp(half_view_h, world_size.y - half_view_h);
         }
 
-        transform.position = desired_position;
+        if transform.position != desired_position {
+            transform.position = desired_position;
+            moved.push(entity);
+        }
 
         // MODIFICATION : Mise à jour de la caméra stockée dans le composant
         camera_comp.camera.target = transform.position;
         camera_comp.camera.rotation = 0.0;
         // On recalcule le zoom à chaque frame au cas où la fenêtre change de taille
         camera_comp.camera.zoom = vec2(
-            camera_comp.zoom * 2.0 / screen_width(), 
-            camera_comp.zoom * 2.0 / screen_height()
+            camera_comp.zoom * 2.0 / screen.width, 
+            camera_comp.zoom * 2.0 / screen.height
         );
 
         // On active la caméra pour le reste de la frame (ou jusqu'au prochain set_camera)
-        set_camera(&camera_comp.camera);
+        if has_window {
+            set_camera(&camera_comp.camera);
+        }
+    }
+
+    for entity in moved {
+        ctx.mark_changed::<Transform>(entity);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hecs::World;
+
+    use super::*;
+    use crate::core::screen::ScreenInfo;
+    use crate::core::time::DeltaTime;
+    use crate::physics::components::PreviousPosition;
+    use crate::prelude::AssetServer;
+
+    #[test]
+    fn camera_follows_the_interpolated_position() {
+        let mut ctx = Context::new(World::new(), AssetServer::new());
+        let mut fixed_time = FixedTime::new(10.0, 4);
+        fixed_time.accumulate(0.15);
+        ctx.insert_resource(fixed_time);
+        ctx.insert_resource(DeltaTime(1.0));
+        ctx.insert_resource(ScreenInfo::new(800.0, 600.0));
+        ctx.insert_resource(Headless);
+
+        // Dernier pas fixe de (0, 0) à (100, 0), rendu à mi-chemin
+        ctx.spawn((
+            Transform { position: vec2(100.0, 0.0), ..Default::default() },
+            PreviousPosition(Vec2::ZERO),
+            CameraTarget
+        ));
+        let camera = ctx.spawn((
+            Transform::default(),
+            CameraComponent { lerp_factor: 100.0, zoom: 1.0, camera: Camera2D::default() },
+            MainCamera
+        ));
+
+        update_camera(&mut ctx);
+
+        let camera_comp = ctx.world.get::<&CameraComponent>(camera).unwrap();
+        assert!((camera_comp.camera.target - vec2(50.0, 0.0)).length() < 0.01, "{:?}", camera_comp.camera.target);
     }
 }
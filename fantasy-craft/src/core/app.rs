@@ -1,18 +1,59 @@
+use std::collections::HashMap;
+
 use hecs::World;
 use macroquad::prelude::*;
 use futures::{FutureExt, future::BoxFuture};
+use crate::core::change_detection::ChangeTicks;
+use crate::core::commands::Commands;
+use crate::core::hooks::ComponentHooks;
 use crate::core::context::Context;
+use crate::core::error::{EngineError, ErrorReport};
 use crate::core::event::EventBus;
+use crate::core::project::ProjectSettings;
+use crate::core::rng::Rng;
 use crate::core::schedule::{Schedule, Stage};
+use crate::core::screen::{ScreenInfo, WindowFocusGained, WindowFocusLost, WindowResized, WindowScaleChanged};
+use crate::core::state::{GameState, StateMachine, States};
 use crate::core::asset_server::AssetServer;
-use crate::core::plugins::Plugin;
-use crate::core::time::DeltaTime;
+use crate::core::plugins::{Plugin, PluginGroup, PluginId};
+use crate::core::time::{DeltaTime, FixedTime, Time};
 use crate::core::web_context::WebContext;
-use crate::graphics::splash_screen::{SplashScreenData, animate_splash_screen, despawn_splash_screen, setup_splash_screen};
+use crate::profiler::resources::Profiler;
+use crate::graphics::splash_screen::{SplashScreenData, animate_splash_screen, despawn_splash_screen, recenter_splash_screen, setup_splash_screen};
 use crate::input::manager::InputManager;
 use crate::prelude::{Spritesheet, System};
 use crate::gui::resources::PreviousMousePosition;
 use crate::scene::scene_loader::SceneLoader;
+use crate::scene::scene_manager::{SceneManager, SceneRequest, despawn_scene_entities, persistent_ids};
+
+/// Event asking the `App` to stop: `run` finishes the current frame, runs the
+/// `OnShutdown` stage and returns. Closing the window sends it as well.
+/// Systems can use `ctx.exit()`.
+#[derive(Debug, Clone, Copy)]
+pub struct AppExit;
+
+/// Resource present while the `App` runs without a window, see `App::step`.
+/// Systems calling macroquad input, drawing or audio functions are skipped
+/// with the `has_window()` run condition.
+#[derive(Debug, Clone, Copy)]
+pub struct Headless;
+
+const DEFAULT_SPLASH_SCREEN_LOGO: &str = "resources/textures/logo_engine.png";
+const DEFAULT_SPLASH_SCREEN_BACKGROUND_COLOR: Color = Color::new(1.0, 0.980392157, 0.960784314, 1.0);
+const DEFAULT_CLEAR_COLOR: Color = LIGHTGRAY;
+
+struct RegisteredPlugin {
+    id: PluginId,
+    // Group plugins it was put in place of, they count as added for the dependency checks
+    replaces: Vec<PluginId>,
+    dependencies: Vec<PluginId>
+}
+
+impl RegisteredPlugin {
+    fn provides(&self, id: PluginId) -> bool {
+        self.id == id || self.replaces.contains(&id)
+    }
+}
 
 pub struct App {
     pub context: Context,
@@ -21,10 +62,19 @@ pub struct App {
     pub window_conf: Conf,
     pub scene_path: Option<String>,
     pub assets_file: Option<String>,
-    show_splash_screen: bool,
-    splash_screen_logo: String,
-    splash_screen_background_color: Color,
-    binding_path: Option<String>
+    show_splash_screen: Option<bool>,
+    splash_screen_logo: Option<String>,
+    splash_screen_background_color: Option<Color>,
+    clear_color: Option<Color>,
+    binding_path: Option<String>,
+    project_file: Option<String>,
+    plugin_settings: HashMap<String, bool>,
+    headless_delta_time: f32,
+    started: bool,
+    exited: bool,
+    pending_plugins: Vec<(PluginId, Box<dyn Plugin>)>,
+    registered_plugins: Vec<RegisteredPlugin>,
+    plugins_built: bool
 }
 
 impl App {
@@ -32,7 +82,7 @@ impl App {
         let world = World::new();
         let asset_server = AssetServer::new();
 
-        App {
+        let mut app = App {
             context: Context::new(
                 world,
                 asset_server
@@ -41,26 +91,52 @@ impl App {
             scene_loader: SceneLoader::new(),
             window_conf: conf,
             scene_path: None,
-            show_splash_screen: true,
+            show_splash_screen: None,
             assets_file: None,
-            splash_screen_logo: "resources/textures/logo_engine.png".to_string(),
-            splash_screen_background_color: Color::new(1.0, 0.980392157, 0.960784314, 1.0),
-            binding_path: None
-        }
+            splash_screen_logo: None,
+            splash_screen_background_color: None,
+            clear_color: None,
+            binding_path: None,
+            project_file: None,
+            plugin_settings: HashMap::new(),
+            headless_delta_time: 1.0 / 60.0,
+            started: false,
+            exited: false,
+            pending_plugins: Vec::new(),
+            registered_plugins: Vec::new(),
+            plugins_built: false
+        };
+
+        app.context.insert_resource(ErrorReport::new());
+        app.context.insert_resource(ComponentHooks::new());
+        app.context.insert_resource(ScreenInfo::new(
+            app.window_conf.window_width as f32,
+            app.window_conf.window_height as f32
+        ));
+
+        // The engine plugins run their systems against the default state type
+        app.add_state(GameState::Playing);
+        app
     }
 
     pub fn with_splash_screen_enabled(&mut self, enabled: bool) -> &mut Self {
-        self.show_splash_screen = enabled;
+        self.show_splash_screen = Some(enabled);
         self
     }
 
     pub fn with_splash_screen_logo(&mut self, path: &str) -> &mut Self {
-        self.splash_screen_logo = path.to_string();
+        self.splash_screen_logo = Some(path.to_string());
         self
     }
 
     pub fn with_splash_screen_background_color(&mut self, color: Color) -> &mut Self {
-        self.splash_screen_background_color = color;
+        self.splash_screen_background_color = Some(color);
+        self
+    }
+
+    /// Color the screen is cleared with at the start of every frame (defaults to `LIGHTGRAY`).
+    pub fn with_clear_color(&mut self, color: Color) -> &mut Self {
+        self.clear_color = Some(color);
         self
     }
 
@@ -79,16 +155,455 @@ impl App {
         self
     }
 
+    /// Reads startup settings from a `project.json` file when `run` starts.
+    /// Values set with the other `with_*` calls take precedence over the file.
+    pub fn with_project_file(&mut self, file_path: String) -> &mut Self {
+        self.project_file = Some(file_path);
+        self
+    }
+
+    /// Fills every setting not already set in code from `settings`.
+    /// `run` calls it with the `with_project_file` content; call it directly to configure a headless app.
+    pub fn with_project_settings(&mut self, settings: ProjectSettings) -> &mut Self {
+        self.show_splash_screen = self.show_splash_screen.or(settings.splash_screen.enabled);
+        self.splash_screen_background_color = self.splash_screen_background_color.or(settings.splash_screen.background_color());
+        self.clear_color = self.clear_color.or(settings.clear_color());
+
+        if self.splash_screen_logo.is_none() {
+            self.splash_screen_logo = settings.splash_screen.logo;
+        }
+        if self.assets_file.is_none() {
+            self.assets_file = settings.assets_file;
+        }
+        if self.scene_path.is_none() {
+            self.scene_path = settings.start_scene;
+        }
+        if self.binding_path.is_none() {
+            self.binding_path = settings.bindings_file;
+        }
+
+        for (name, enabled) in settings.plugins {
+            self.plugin_settings.entry(name).or_insert(enabled);
+        }
+
+        self
+    }
+
+    /// Enables or disables a plugin by name (e.g. `"DebugPlugin"`) without removing the `add_plugin` call.
+    /// Disabled plugins are skipped by `build_plugins`.
+    pub fn with_plugin_enabled(&mut self, name: &str, enabled: bool) -> &mut Self {
+        self.plugin_settings.insert(name.to_string(), enabled);
+        self
+    }
+
+    /// Seeds the `Rng` resource so that every run draws the same random numbers.
+    pub fn with_seed(&mut self, seed: u64) -> &mut Self {
+        self.context.insert_resource(Rng::new(seed));
+        self
+    }
+
+    /// Sets the synthetic frame duration used by `step` (defaults to 1/60s).
+    pub fn with_headless_delta_time(&mut self, delta_time: f32) -> &mut Self {
+        self.headless_delta_time = delta_time;
+        self
+    }
+
+    /// Records when `T` is added, modified or removed so that systems can use
+    /// `ctx.added::<T>()`, `ctx.changed::<T>()` and `ctx.removed::<T>()`.
+    /// Systems writing `T` in place report it with `ctx.mark_changed::<T>(entity)`.
+    pub fn track_changes<T: hecs::Component>(&mut self) -> &mut Self {
+        if self.context.get_resource::<ChangeTicks>().is_none() {
+            self.context.insert_resource(ChangeTicks::new());
+        }
+
+        self.context.resource_mut::<ChangeTicks>().track::<T>();
+        self
+    }
+
+    /// Runs `hook` whenever `T` is added to an entity through `Context`, `Commands` or a scene,
+    /// e.g. to insert companion components right away.
+    pub fn on_add<T: hecs::Component>(&mut self, hook: impl Fn(&mut Context, hecs::Entity) + Send + Sync + 'static) -> &mut Self {
+        self.component_hooks().on_add::<T>(hook);
+        self
+    }
+
+    /// Runs `hook` right before `T` is removed from an entity or the entity is despawned
+    /// through `Context` or `Commands`, e.g. to clean up a cache.
+    pub fn on_remove<T: hecs::Component>(&mut self, hook: impl Fn(&mut Context, hecs::Entity) + Send + Sync + 'static) -> &mut Self {
+        self.component_hooks().on_remove::<T>(hook);
+        self
+    }
+
+    fn component_hooks(&mut self) -> &mut ComponentHooks {
+        if self.context.get_resource::<ComponentHooks>().is_none() {
+            self.context.insert_resource(ComponentHooks::new());
+        }
+
+        self.context.resource_mut::<ComponentHooks>()
+    }
+
+    /// Registers an independent state machine for `S`, starting in `initial`.
+    /// Use `Stage::on_enter` / `Stage::on_exit` and `System::new(.., vec![state])` with it.
+    pub fn add_state<S: States>(&mut self, initial: S) -> &mut Self {
+        if self.context.get_resource::<StateMachine<S>>().is_none() {
+            self.schedule.add_state_type::<S>();
+        }
+
+        self.context.insert_resource(StateMachine::new(initial));
+        self
+    }
+
     pub fn add_system(&mut self, stage: Stage, system: System) -> &mut Self {
         self.schedule.add_system(stage, system);
         self
     }
 
+    /// Registers a plugin. Plugins are built in order when the app starts,
+    /// or right away when added after that (e.g. from another plugin's `build`).
     pub fn add_plugin<T: Plugin>(&mut self, plugin: T) -> &mut Self {
-        plugin.build(self);
+        self.add_boxed_plugin(PluginId::of::<T>(), Vec::new(), Box::new(plugin));
         self
     }
 
+    /// Adds every enabled plugin of a group, e.g. `Default2dPlugin`.
+    pub fn add_plugins<G: PluginGroup>(&mut self, group: G) -> &mut Self {
+        group.build().finish(self);
+        self
+    }
+
+    /// True as well when `T` was replaced in a plugin group.
+    pub fn is_plugin_added<T: Plugin>(&self) -> bool {
+        let id = PluginId::of::<T>();
+        self.registered_plugins.iter().any(|registered| registered.provides(id))
+    }
+
+    /// `replaces` are the plugins `plugin` stands in for in a group, see `PluginGroupBuilder::replace`.
+    pub(crate) fn add_boxed_plugin(&mut self, id: PluginId, replaces: Vec<PluginId>, plugin: Box<dyn Plugin>) {
+        if plugin.is_unique() && self.registered_plugins.iter().any(|registered| registered.id == id) {
+            panic!("App: Plugin '{}' was added twice", plugin.name());
+        }
+
+        self.registered_plugins.push(RegisteredPlugin {
+            id,
+            replaces,
+            dependencies: plugin.dependencies()
+        });
+
+        if self.plugins_built {
+            if !self.is_plugin_enabled(plugin.as_ref()) {
+                self.skip_plugin(id, plugin.as_ref());
+                return;
+            }
+
+            plugin.build(self);
+            self.check_plugin_dependencies();
+        } else {
+            self.pending_plugins.push((id, plugin));
+        }
+    }
+
+    /// Builds the pending plugins and checks that their dependencies were added.
+    /// Called automatically by `run` and `step`.
+    pub fn build_plugins(&mut self) {
+        if self.plugins_built {
+            return;
+        }
+
+        // Plugins added while building are built at the end of the current batch
+        while !self.pending_plugins.is_empty() {
+            for (id, plugin) in std::mem::take(&mut self.pending_plugins) {
+                if self.is_plugin_enabled(plugin.as_ref()) {
+                    plugin.build(self);
+                } else {
+                    self.skip_plugin(id, plugin.as_ref());
+                }
+            }
+        }
+
+        self.plugins_built = true;
+        self.check_plugin_dependencies();
+    }
+
+    fn is_plugin_enabled(&self, plugin: &dyn Plugin) -> bool {
+        self.plugin_settings.get(plugin.name()).copied().unwrap_or(true)
+    }
+
+    /// Forgets the registration of the disabled `plugin` only, other plugins of the same name are kept.
+    fn skip_plugin(&mut self, id: PluginId, plugin: &dyn Plugin) {
+        info!("App: Plugin '{}' is disabled", plugin.name());
+
+        if let Some(index) = self.registered_plugins.iter().rposition(|registered| registered.id == id) {
+            self.registered_plugins.remove(index);
+        }
+    }
+
+    fn check_plugin_dependencies(&self) {
+        let missing: Vec<String> = self.registered_plugins.iter()
+            .flat_map(|plugin| plugin.dependencies.iter()
+                .filter(|dependency| !self.registered_plugins.iter().any(|registered| registered.provides(**dependency)))
+                .map(move |dependency| format!("'{}' requires '{}'", plugin.id.name(), dependency.name())))
+            .collect();
+
+        if !missing.is_empty() {
+            panic!("App: Missing plugin dependencies: {}", missing.join(", "));
+        }
+    }
+
+    /// Sorts the schedule, runs the `StartUp` stage once and enters the initial state.
+    /// Called automatically by `run` and `step`.
+    pub fn startup(&mut self) {
+        if self.started {
+            return;
+        }
+
+        self.started = true;
+        self.build_plugins();
+
+        // Systems spawn scenes with the loaders registered by the plugins
+        self.context.insert_resource(self.scene_loader.clone());
+
+        if let Err(e) = self.schedule.build() {
+            panic!("App: Invalid system schedule: {}", e);
+        }
+
+        self.schedule.run_stage(Stage::StartUp, &mut self.context);
+
+        self.schedule.enter_initial_states(&mut self.context);
+        self.schedule.apply_state_transitions(&mut self.context);
+    }
+
+    /// Advances the app by `frames` frames without a window.
+    ///
+    /// Each frame uses the synthetic delta time set with `with_headless_delta_time`
+    /// and runs every stage from `Update` to `GuiRender`. The `Headless` resource is inserted,
+    /// so the systems that need a window (input, drawing, audio) are skipped through `has_window()`.
+    /// The splash screen and asset loading are skipped as well, which makes this suitable
+    /// for integration tests and CI machines without a display.
+    /// Stops early once an `AppExit` was handled.
+    pub fn step(&mut self, frames: u32) {
+        if !self.started {
+            self.context.insert_resource(Headless);
+        }
+
+        self.startup();
+
+        for _ in 0..frames {
+            if self.exited {
+                return;
+            }
+
+            self.advance_time(self.headless_delta_time);
+            self.run_update_stages();
+            self.run_render_stages();
+
+            let exit_requested = self.exit_requested();
+            self.end_frame();
+
+            if exit_requested {
+                self.shutdown();
+            }
+        }
+    }
+
+    /// True once an `AppExit` was handled and the `OnShutdown` stage ran.
+    pub fn has_exited(&self) -> bool {
+        self.exited
+    }
+
+    fn exit_requested(&self) -> bool {
+        self.context.get_resource::<EventBus>()
+            .is_some_and(|event_bus| event_bus.read::<AppExit>().next().is_some())
+    }
+
+    fn shutdown(&mut self) {
+        if self.exited {
+            return;
+        }
+
+        info!("App: Shutting down");
+        self.schedule.run_stage(Stage::OnShutdown, &mut self.context);
+        self.exited = true;
+    }
+
+    /// Starts a new frame on the `Time` clock and exposes its scaled delta as `DeltaTime`.
+    fn advance_time(&mut self, frame_delta: f32) {
+        if self.context.get_resource::<Time>().is_none() {
+            self.context.insert_resource(Time::new());
+        }
+
+        let time = self.context.resource_mut::<Time>();
+        time.advance(frame_delta);
+        let delta_time = time.delta();
+
+        self.set_delta_time(delta_time);
+    }
+
+    /// Refreshes `ScreenInfo` from the window and sends the matching window events.
+    fn update_screen_info(&mut self) {
+        let current = ScreenInfo::current();
+        let previous = self.context.get_resource::<ScreenInfo>().copied();
+        self.context.insert_resource(current);
+
+        let (Some(previous), Some(event_bus)) = (previous, self.context.get_resource_mut::<EventBus>()) else {
+            return;
+        };
+
+        if previous.width != current.width || previous.height != current.height {
+            event_bus.send(WindowResized {
+                width: current.width,
+                height: current.height
+            });
+        }
+
+        if previous.dpi_scale != current.dpi_scale {
+            event_bus.send(WindowScaleChanged {
+                dpi_scale: current.dpi_scale
+            });
+        }
+
+        match (previous.focused, current.focused) {
+            (true, false) => event_bus.send(WindowFocusLost),
+            (false, true) => event_bus.send(WindowFocusGained),
+            _ => {}
+        }
+    }
+
+    fn set_delta_time(&mut self, delta_time: f32) {
+        if let Some(dt) = self.context.get_resource_mut::<DeltaTime>() {
+            dt.0 = delta_time;
+        } else {
+            self.context.insert_resource(DeltaTime(delta_time));
+        }
+    }
+
+    fn run_update_stages(&mut self) {
+        self.schedule.run_stage(Stage::Update, &mut self.context);
+        self.run_fixed_update();
+        self.schedule.run_stage(Stage::PostUpdate, &mut self.context);
+    }
+
+    /// Runs `FixedUpdate` as many times as the accumulated frame time allows.
+    /// `DeltaTime` holds the fixed timestep while the stage runs.
+    /// The scaled frame time is accumulated, so slow-motion means fewer ticks and pause means none.
+    fn run_fixed_update(&mut self) {
+        let frame_delta = self.context.dt();
+
+        if self.context.get_resource::<FixedTime>().is_none() {
+            self.context.insert_resource(FixedTime::default());
+        }
+
+        let fixed_time = self.context.resource_mut::<FixedTime>();
+        let steps = fixed_time.accumulate(frame_delta);
+        let timestep = fixed_time.timestep;
+
+        for _ in 0..steps {
+            self.set_delta_time(timestep);
+            self.schedule.run_stage(Stage::FixedUpdate, &mut self.context);
+        }
+
+        self.set_delta_time(frame_delta);
+    }
+
+    fn run_render_stages(&mut self) {
+        let headless = self.context.get_resource::<Headless>().is_some();
+
+        self.schedule.run_stage(Stage::Render, &mut self.context);
+        self.schedule.run_stage(Stage::PostRender, &mut self.context);
+
+        if !headless {
+            set_default_camera();
+        }
+        self.schedule.run_stage(Stage::GuiRender, &mut self.context);
+
+        if headless {
+            return;
+        }
+
+        if let Some(scene_manager) = self.context.get_resource::<SceneManager>() {
+            scene_manager.draw_fade(&self.context.screen());
+        }
+
+        if let Some(error_report) = self.context.get_resource::<ErrorReport>() {
+            error_report.draw(&self.context.screen());
+        }
+    }
+
+    fn end_frame(&mut self) {
+        // State changes happen between two frames so every system of a frame sees the same state.
+        // Applied before the profiler frame is closed, so `OnEnter` / `OnExit` count in this frame.
+        self.schedule.apply_state_transitions(&mut self.context);
+
+        let entity_count = self.context.world.len();
+        let event_count = self.context.get_resource::<EventBus>()
+            .map_or(0, |event_bus| event_bus.sent_count());
+
+        if let Some(event_bus) = self.context.get_resource_mut::<EventBus>() {
+            event_bus.update();
+        }
+
+        if let Some(profiler) = self.context.get_resource_mut::<Profiler>()
+            && profiler.enabled {
+            profiler.end_frame(entity_count, event_count);
+        }
+    }
+
+    /// Runs the `SceneManager` requests and the scenes queued with `Commands::spawn_scene` during the frame.
+    /// `step` cannot load files without a window, so requests stay queued in headless mode.
+    async fn process_scene_requests(&mut self) {
+        let spawn_requests = self.context.get_resource_mut::<Commands>()
+            .map(|commands| commands.take_scene_requests())
+            .unwrap_or_default();
+
+        let unscaled_dt = self.context.unscaled_dt();
+        let Some(scene_manager) = self.context.get_resource_mut::<SceneManager>() else {
+            return;
+        };
+
+        for path in spawn_requests {
+            scene_manager.load(&path);
+        }
+        scene_manager.update_fade(unscaled_dt);
+
+        while let Some(request) = self.context.resource_mut::<SceneManager>().next_request() {
+            match request {
+                SceneRequest::Load(path) => self.load_scene(&path).await,
+                SceneRequest::Unload(path) => {
+                    let entities = self.context.resource_mut::<SceneManager>().untrack(&path);
+                    despawn_scene_entities(&mut self.context, entities);
+                },
+                SceneRequest::Replace(path) => {
+                    let entities = self.context.resource_mut::<SceneManager>().untrack_all();
+                    despawn_scene_entities(&mut self.context, entities);
+                    self.load_scene(&path).await;
+                }
+            }
+        }
+    }
+
+    /// Loads the scene at `path`, reusing the `Persistent` entities, and records it in the `SceneManager`.
+    async fn load_scene(&mut self, path: &str) {
+        let resolved_path = Self::resolve_path(&WebContext::get_base_url(), path);
+        let reused = persistent_ids(&self.context);
+
+        match self.scene_loader.load_scene_reusing(&resolved_path, &mut self.context, reused).await {
+            Ok(entities) => {
+                if let Some(scene_manager) = self.context.get_resource_mut::<SceneManager>() {
+                    scene_manager.track(path, entities);
+                }
+            },
+            Err(e) => self.report_error(e)
+        }
+    }
+
+    /// Adds `error` to the on-screen `ErrorReport` instead of aborting.
+    fn report_error(&mut self, error: EngineError) {
+        if self.context.get_resource::<ErrorReport>().is_none() {
+            self.context.insert_resource(ErrorReport::new());
+        }
+
+        self.context.resource_mut::<ErrorReport>().push(error);
+    }
+
     fn resolve_path(base: &str, path: &str) -> String {
         if path.starts_with("http") {
             return path.to_string();
@@ -101,19 +616,39 @@ impl App {
         let base_url = WebContext::get_base_url();
         info!("App: Resolved Base URL: {}", base_url);
 
+        // The project file may disable plugins, so it is read before they are built
+        if let Some(project_file) = self.project_file.clone() {
+            let resolved_project_file = Self::resolve_path(&base_url, &project_file);
+            info!("App: Loading project settings from: {}", resolved_project_file);
+
+            match ProjectSettings::load(&resolved_project_file).await {
+                Ok(settings) => {
+                    self.with_project_settings(settings);
+                },
+                Err(e) => self.report_error(e)
+            }
+        }
+
+        self.build_plugins();
+
+        // The window exists now, replace the size requested in `window_conf` by the real one
+        self.context.insert_resource(ScreenInfo::current());
+
         // 2. Resolve paths using the base URL
-        let resolved_splash_path = Self::resolve_path(&base_url, &self.splash_screen_logo);
+        let splash_screen_logo = self.splash_screen_logo.as_deref().unwrap_or(DEFAULT_SPLASH_SCREEN_LOGO);
+        let resolved_splash_path = Self::resolve_path(&base_url, splash_screen_logo);
+        let splash_background_color = self.splash_screen_background_color.unwrap_or(DEFAULT_SPLASH_SCREEN_BACKGROUND_COLOR);
+        let clear_color = self.clear_color.unwrap_or(DEFAULT_CLEAR_COLOR);
         
         // Resolve optional paths
         let resolved_assets_file = self.assets_file.as_ref().map(|p| Self::resolve_path(&base_url, p));
-        let resolved_scene_path = self.scene_path.as_ref().map(|p| Self::resolve_path(&base_url, p));
         let resolved_binding_path = self.binding_path.as_ref().map(|p| Self::resolve_path(&base_url, p));
 
         const SPLASH_MIN_DURATION: f64 = 3.0;
 
         let mut maybe_asset_server: Option<AssetServer> = None;
 
-        if self.show_splash_screen {
+        if self.show_splash_screen.unwrap_or(true) {
             let splash_texture = match load_texture(&resolved_splash_path).await {
                 Ok(tex) => tex,
                 Err(e) => {
@@ -135,7 +670,7 @@ impl App {
             );
 
             self.context.insert_resource(SplashScreenData {
-                background_color: self.splash_screen_background_color
+                background_color: splash_background_color
             });
 
             setup_splash_screen(&mut self.context);
@@ -146,7 +681,7 @@ impl App {
 
             let asset_path_for_future = resolved_assets_file.clone();
 
-            let mut load_future: BoxFuture<'static, (AssetServer, Result<(), Box<dyn std::error::Error>>)> =
+            let mut load_future: BoxFuture<'static, (AssetServer, Result<(), EngineError>)> =
                 Box::pin(async move {
                     let result = if let Some(path) = asset_path_for_future {
                         loading_asset_server.load_assets_from_file(&path).await
@@ -160,16 +695,19 @@ impl App {
 
             // --- Boucle du splash ---
             loop {
-                let dt = self.context.resource_mut::<DeltaTime>();
-                dt.0 = get_frame_time();
-                clear_background(self.splash_screen_background_color);
+                self.advance_time(get_frame_time());
+                self.update_screen_info();
+                clear_background(splash_background_color);
 
                 // Animation + rendu
+                recenter_splash_screen(&mut self.context);
                 animate_splash_screen(&mut self.context);
-                self.schedule.run_stage(Stage::Render, &mut self.context);
-                self.schedule.run_stage(Stage::PostRender, &mut self.context);
-                set_default_camera();
-                self.schedule.run_stage(Stage::GuiRender, &mut self.context);
+                self.run_render_stages();
+
+                // Only the window events are sent during the splash screen
+                if let Some(event_bus) = self.context.get_resource_mut::<EventBus>() {
+                    event_bus.update();
+                }
 
                 next_frame().await;
 
@@ -178,9 +716,9 @@ impl App {
 
                 if !assets_loaded {
                     if let Some((loaded_server, result)) = load_future.as_mut().now_or_never() {
-                        // Log error instead of crashing if assets.json is missing
+                        // Report the error instead of crashing if assets.json is missing
                         if let Err(e) = result {
-                             error!("Failed to load assets from JSON file: {}", e);
+                            self.report_error(e);
                         }
                         maybe_asset_server = Some(loaded_server);
                         assets_loaded = true;
@@ -199,7 +737,7 @@ impl App {
 
             if let Some(path) = &resolved_assets_file {
                 if let Err(e) = asset_server.load_assets_from_file(path).await {
-                     error!("Failed to load assets from JSON file: {}", e);
+                    self.report_error(e);
                 }
             }
 
@@ -212,10 +750,14 @@ impl App {
             self.context.asset_server.finalize_textures().await;
         }
 
+        for e in self.context.asset_server.take_load_errors() {
+            self.report_error(e);
+        }
+
         self.context.asset_server.prepare_loaded_tiledmap().await;
 
-        if let Some(scene_path) = resolved_scene_path {
-            self.scene_loader.load_scene_from_file(&scene_path, &mut self.context).await.unwrap();
+        if let Some(scene_path) = self.scene_path.clone() {
+            self.load_scene(&scene_path).await;
         }
 
         if let Some(binding_path) = resolved_binding_path {
@@ -230,31 +772,35 @@ impl App {
                     }
                 },
                 Err(e) => {
-                    error!("App: Failed to load binding file '{}': {}", binding_path, e);
+                    self.report_error(EngineError::io(&binding_path, e));
                 }
             }
         }
 
         // --- Démarrage du jeu ---
-        self.schedule.run_stage(Stage::StartUp, &mut self.context);
+        self.startup();
+
+        // Closing the window goes through AppExit so the OnShutdown stage can run
+        prevent_quit();
 
         loop {
-            let dt = self.context.resource_mut::<DeltaTime>();
-            dt.0 = get_frame_time();
-            clear_background(LIGHTGRAY);
+            self.advance_time(get_frame_time());
+            self.update_screen_info();
+            clear_background(clear_color);
 
-            self.schedule.run_stage(Stage::Update, &mut self.context);
-            self.schedule.run_stage(Stage::PostUpdate, &mut self.context);
-            self.schedule.run_stage(Stage::Render, &mut self.context);
-            self.schedule.run_stage(Stage::PostRender, &mut self.context);
+            self.run_update_stages();
+            self.run_render_stages();
 
-            set_default_camera();
-            self.schedule.run_stage(Stage::GuiRender, &mut self.context);
+            let exit_requested = self.exit_requested() || is_quit_requested();
+            self.end_frame();
 
-            if let Some(event_bus) = self.context.get_resource_mut::<EventBus>() {
-                event_bus.clear();
+            if exit_requested {
+                self.shutdown();
+                return;
             }
 
+            self.process_scene_requests().await;
+
             if let Some(prev_mouse_pos) = self.context.get_resource_mut::<PreviousMousePosition>() {
                 prev_mouse_pos.0 = mouse_position().into();
             }
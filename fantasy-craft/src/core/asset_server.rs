@@ -9,6 +9,9 @@ use crate::graphics::tiled_map::tiled_map::{Tileset, TileMap, RenderedTileMap};
 use crate::graphics::tiled_map::serializers::{LayerData, TiledMapData};
 // Assure-toi d'importer WebContext
 use crate::core::web_context::WebContext; 
+use crate::core::error::EngineError;
+use crate::scene::scene_format::SceneFile;
+use crate::scene::scene_loader::fetch_scene_files;
 
 #[derive(Deserialize)]
 struct MapData {
@@ -51,6 +54,12 @@ struct SoundData {
     path: String
 }
 
+#[derive(Deserialize)]
+struct SceneData {
+    id: String,
+    path: String
+}
+
 #[derive(Deserialize, Default)]
 struct AssetFileData {
     #[serde(default)]
@@ -66,7 +75,10 @@ struct AssetFileData {
     animations: Vec<AnimationData>,
 
     #[serde(default)]
-    sounds: Vec<SoundData>
+    sounds: Vec<SoundData>,
+
+    #[serde(default)]
+    scenes: Vec<SceneData>
 }
 
 // --- AssetServer ---
@@ -77,7 +89,12 @@ pub struct AssetServer {
     rendered_maps: HashMap<String, RenderedTileMap>,
     rendered_layers: HashMap<String, HashMap<String, RenderTarget>>,
     fonts: HashMap<String, Font>,
-    sounds: HashMap<String, Sound>
+    sounds: HashMap<String, Sound>,
+    // Scene paths by id, and the parsed files by path (with their imports and prefabs)
+    scenes: HashMap<String, String>,
+    scene_files: HashMap<String, Arc<SceneFile>>,
+    // Assets that failed to load, the others are still available
+    load_errors: Vec<EngineError>
 }
 
 #[allow(dead_code)]
@@ -90,7 +107,10 @@ impl AssetServer {
             rendered_maps: HashMap::new(),
             rendered_layers: HashMap::new(),
             fonts: HashMap::new(),
-            sounds: HashMap::new()
+            sounds: HashMap::new(),
+            scenes: HashMap::new(),
+            scene_files: HashMap::new(),
+            load_errors: Vec::new()
         }
     }
 
@@ -145,7 +165,10 @@ impl AssetServer {
             Ok(sound) => {
                 self.sounds.insert(name.to_string(), sound);
             }
-            Err(e) => error!("Failed to load sound {}: {:?}", path, e)
+            Err(e) => {
+                error!("Failed to load sound {}: {:?}", path, e);
+                self.load_errors.push(EngineError::io(path, e));
+            }
         }
     }
 
@@ -153,25 +176,64 @@ impl AssetServer {
         self.sounds.get(name)
     }
 
+    pub fn sounds(&self) -> impl Iterator<Item = &Sound> {
+        self.sounds.values()
+    }
+
+    /// Reads and parses the scene at `path` and the files it imports, so it can be spawned
+    /// without waiting with `SceneLoader::spawn_scene`.
+    pub async fn load_scene(&mut self, id: &str, path: &str) {
+        for (file_path, result) in fetch_scene_files(path).await {
+            match result {
+                Ok(scene) => {
+                    self.scene_files.insert(file_path, scene);
+                },
+                Err(e) => {
+                    error!("Failed to load scene {}: {}", file_path, e);
+                    self.load_errors.push(e);
+                }
+            }
+        }
+
+        self.scenes.insert(id.to_string(), path.to_string());
+    }
+
+    pub fn get_scene(&self, id: &str) -> Option<&Arc<SceneFile>> {
+        self.scene_files.get(self.scenes.get(id)?)
+    }
+
+    pub fn get_scene_path(&self, id: &str) -> Option<&str> {
+        self.scenes.get(id).map(String::as_str)
+    }
+
+    /// Every cached scene file by path, imports and prefabs included.
+    pub fn scene_files(&self) -> impl Iterator<Item = (&str, &Arc<SceneFile>)> {
+        self.scene_files.iter().map(|(path, scene)| (path.as_str(), scene))
+    }
+
     pub fn merge(&mut self, other: AssetServer) {
         self.animations.extend(other.animations);
         self.spritesheets.extend(other.spritesheets);
         self.fonts.extend(other.fonts);
         self.sounds.extend(other.sounds);
         self.maps.extend(other.maps);
+        self.scenes.extend(other.scenes);
+        self.scene_files.extend(other.scene_files);
+        self.load_errors.extend(other.load_errors);
     }
 
-    pub async fn load_tiled_map(&mut self, id: String, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    /// Returns the errors raised by the assets that failed to load since the last call.
+    pub fn take_load_errors(&mut self) -> Vec<EngineError> {
+        std::mem::take(&mut self.load_errors)
+    }
+
+    pub async fn load_tiled_map(&mut self, id: String, path: &str) -> Result<(), EngineError> {
         // CORRECTION MAJEURE : Utilisation de load_string (HTTP) au lieu de std::fs (Disque)
-        let json_content = match load_string(path).await {
-            Ok(s) => s,
-            Err(e) => {
-                error!("Impossible de charger la map {}. Erreur: {}", path, e);
-                return Err(Box::new(e));
-            }
-        };
+        let json_content = load_string(path).await
+            .map_err(|e| EngineError::io(path, e))?;
 
-        let map_data: TiledMapData = serde_json::from_str(&json_content)?;
+        let map_data: TiledMapData = serde_json::from_str(&json_content)
+            .map_err(|e| EngineError::parse(path, e))?;
 
         let map_path = Path::new(path);
         let map_dir = map_path.parent().unwrap_or(Path::new(""));
@@ -182,11 +244,12 @@ impl AssetServer {
         for ts_data in &map_data.tilesets {
             let relative_image_path = Path::new(&ts_data.image); 
             let absolute_image_path: PathBuf = map_dir.join(relative_image_path);
-            let tileset_path = absolute_image_path.to_str().unwrap().to_string();
+            let tileset_path = absolute_image_path.to_string_lossy().to_string();
 
             if !self.spritesheets.contains_key(&tileset_path) {
                 // Ici, load_texture utilisera l'URL complète car tileset_path est dérivé de path (qui est déjà une URL)
-                let texture = load_texture(&tileset_path).await?;
+                let texture = load_texture(&tileset_path).await
+                    .map_err(|e| EngineError::io(&tileset_path, e))?;
                 texture.set_filter(FilterMode::Nearest);
 
                 let tile_w = ts_data.tilewidth as f32;
@@ -195,7 +258,9 @@ impl AssetServer {
                 self.add_spritesheet(tileset_path.clone(), spritesheet);
             }
 
-            let spritesheet_arc = self.spritesheets.get(&tileset_path).unwrap().clone();
+            let spritesheet_arc = self.spritesheets.get(&tileset_path)
+                .ok_or_else(|| EngineError::missing_asset("spritesheet", &tileset_path))?
+                .clone();
 
             tilesets.push(Tileset {
                 first_gid: ts_data.firstgid,
@@ -269,26 +334,27 @@ impl AssetServer {
     }
 
     // --- Logique de chargement principale ---
-    pub async fn load_assets_from_file(&mut self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    // Fails only if the asset file itself cannot be read or parsed.
+    // Individual assets that fail are skipped and kept in `take_load_errors`.
+    pub async fn load_assets_from_file(&mut self, path: &str) -> Result<(), EngineError> {
         // 1. Récupération de l'URL de base depuis le JS (ou vide sur PC)
         let base_url = WebContext::get_base_url();
         info!("AssetServer Base URL: {}", base_url);
 
         // Note: 'path' ici est déjà résolu dans App::run, donc load_string fonctionnera
-        let json_content = match load_string(path).await {
-            Ok(s) => s,
-            Err(e) => {
-                error!("AssetServer: Failed to download/read file '{}'. Error: {}", path, e);
-                return Err(Box::new(e));
-            }
-        };
-        let asset_data: AssetFileData = serde_json::from_str(&json_content)?;
+        let json_content = load_string(path).await
+            .map_err(|e| EngineError::io(path, e))?;
+        let asset_data: AssetFileData = serde_json::from_str(&json_content)
+            .map_err(|e| EngineError::parse(path, e))?;
 
         // 2. Chargement des Maps (on résout le chemin)
         for map_data in asset_data.maps {
             let resolved_path = Self::resolve_path(&base_url, &map_data.path);
             info!("Loading Map: {} from {}", map_data.id, resolved_path);
-            self.load_tiled_map(map_data.id, &resolved_path).await.unwrap();
+            if let Err(e) = self.load_tiled_map(map_data.id, &resolved_path).await {
+                error!("Failed to load map {}. Error: {}", resolved_path, e);
+                self.load_errors.push(e);
+            }
         }
 
         // 3. Chargement des Spritesheets (on résout le chemin)
@@ -300,6 +366,7 @@ impl AssetServer {
                 Ok(t) => t,
                 Err(e) => {
                     error!("Failed to load texture {}. Error: {}", resolved_path, e);
+                    self.load_errors.push(EngineError::io(&resolved_path, e));
                     continue; // Skip sans crasher
                 }
             };
@@ -316,10 +383,14 @@ impl AssetServer {
         // 4. Création des Animations (Pas besoin de path ici, c'est des IDs)
         for anim_data in asset_data.animations {
             // ... (code inchangé, logique pure) ...
-            let spritesheet_arc = self.spritesheets.get(&anim_data.spritesheet_id)
-                .ok_or_else(|| format!("Spritesheet '{}' not found for animation '{}'", 
-                                       anim_data.spritesheet_id, anim_data.id))?
-                .clone();
+            let spritesheet_arc = match self.spritesheets.get(&anim_data.spritesheet_id) {
+                Some(spritesheet) => spritesheet.clone(),
+                None => {
+                    error!("Spritesheet '{}' not found for animation '{}'", anim_data.spritesheet_id, anim_data.id);
+                    self.load_errors.push(EngineError::missing_asset("spritesheet", &anim_data.spritesheet_id));
+                    continue;
+                }
+            };
 
             let frames: Vec<AnimationKeyFrame> = (0..anim_data.frame_sequence.count)
                 .map(|col| AnimationKeyFrame::new(col, anim_data.frame_sequence.row))
@@ -343,10 +414,14 @@ impl AssetServer {
             let resolved_path = Self::resolve_path(&base_url, &font_data.path);
             info!("Loading Font: {} from {}", font_data.id, resolved_path);
             
-            if let Ok(font) = load_ttf_font(&resolved_path).await {
-                 self.fonts.insert(font_data.id.clone(), font);
-            } else {
-                error!("Failed to load font {}", resolved_path);
+            match load_ttf_font(&resolved_path).await {
+                Ok(font) => {
+                    self.fonts.insert(font_data.id.clone(), font);
+                },
+                Err(e) => {
+                    error!("Failed to load font {}", resolved_path);
+                    self.load_errors.push(EngineError::io(&resolved_path, e));
+                }
             }
         }
 
@@ -357,6 +432,13 @@ impl AssetServer {
             self.load_sound(&sound_data.id, &resolved_path).await;
         }
 
+        // 7. Scenes spawned at runtime, parsed now so that spawning them does not wait
+        for scene_data in asset_data.scenes {
+            let resolved_path = Self::resolve_path(&base_url, &scene_data.path);
+            info!("Loading Scene: {} from {}", scene_data.id, resolved_path);
+            self.load_scene(&scene_data.id, &resolved_path).await;
+        }
+
         Ok(())
     }
 
@@ -0,0 +1,254 @@
+use std::any::{TypeId, type_name};
+use std::collections::HashMap;
+
+use hecs::{Component, Entity};
+
+/// Monotonic counter advanced after every system run.
+pub type Tick = u64;
+
+/// Removals older than this many ticks are forgotten.
+const REMOVED_RETENTION: Tick = 4096;
+
+/// When a tracked component was added to an entity and last modified.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ComponentTicks {
+    pub added: Tick,
+    pub changed: Tick
+}
+
+#[derive(Default)]
+struct TrackedComponent {
+    ticks: HashMap<Entity, ComponentTicks>,
+    removed: HashMap<Entity, Tick>
+}
+
+/// Resource recording when tracked components were added, changed or removed.
+///
+/// Additions and removals are stamped by `Context` (`spawn`, `insert`, `remove`, `despawn`),
+/// `Commands` and the scene loader. hecs has no write hooks, so a system that modifies a tracked
+/// component in place must call `ctx.mark_changed::<T>(entity)`.
+/// Each system runs at its own tick: its writes are seen by every other system on their next run,
+/// but not by the writer itself. Components must opt in with `App::track_changes`.
+pub struct ChangeTicks {
+    tick: Tick,
+    trackers: HashMap<TypeId, TrackedComponent>
+}
+
+impl Default for ChangeTicks {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ChangeTicks {
+    pub fn new() -> Self {
+        Self {
+            // Systems start with a last run of 0, so they see everything stamped before their first run
+            tick: 1,
+            trackers: HashMap::new()
+        }
+    }
+
+    /// Starts tracking `T`. Only the additions made after this call are recorded.
+    pub fn track<T: Component>(&mut self) {
+        self.trackers.entry(TypeId::of::<T>()).or_default();
+    }
+
+    pub fn is_tracked<T: Component>(&self) -> bool {
+        self.trackers.contains_key(&TypeId::of::<T>())
+    }
+
+    pub fn current_tick(&self) -> Tick {
+        self.tick
+    }
+
+    /// Ends the current tick and returns it, called after each system run.
+    pub(crate) fn advance(&mut self) -> Tick {
+        let tick = self.tick;
+        self.tick += 1;
+
+        if self.tick.is_multiple_of(REMOVED_RETENTION) {
+            for tracker in self.trackers.values_mut() {
+                tracker.removed.retain(|_, removed_at| tick.saturating_sub(*removed_at) <= REMOVED_RETENTION);
+            }
+        }
+
+        tick
+    }
+
+    /// Records that `entity`'s `T` was modified. Does nothing if `T` is not tracked.
+    pub fn mark_changed<T: Component>(&mut self, entity: Entity) {
+        self.stamp_inserted(TypeId::of::<T>(), entity);
+    }
+
+    /// Stamps a component that was inserted or modified: added if `entity` did not have it yet.
+    pub(crate) fn stamp_inserted(&mut self, type_id: TypeId, entity: Entity) {
+        let tick = self.tick;
+
+        if let Some(tracker) = self.trackers.get_mut(&type_id) {
+            tracker.ticks.entry(entity)
+                .and_modify(|ticks| ticks.changed = tick)
+                .or_insert(ComponentTicks { added: tick, changed: tick });
+            tracker.removed.remove(&entity);
+        }
+    }
+
+    pub(crate) fn stamp_removed(&mut self, type_id: TypeId, entity: Entity) {
+        let tick = self.tick;
+
+        if let Some(tracker) = self.trackers.get_mut(&type_id)
+            && tracker.ticks.remove(&entity).is_some() {
+            tracker.removed.insert(entity, tick);
+        }
+    }
+
+    /// Stamps the removal of every tracked component of a despawned entity.
+    pub(crate) fn stamp_despawned(&mut self, entity: Entity) {
+        let tick = self.tick;
+
+        for tracker in self.trackers.values_mut() {
+            if tracker.ticks.remove(&entity).is_some() {
+                tracker.removed.insert(entity, tick);
+            }
+        }
+    }
+
+    pub fn ticks<T: Component>(&self, entity: Entity) -> Option<ComponentTicks> {
+        self.tracker::<T>().ticks.get(&entity).copied()
+    }
+
+    /// Entities that received `T` after `since`.
+    pub fn added<T: Component>(&self, since: Tick) -> Vec<Entity> {
+        self.tracker::<T>().ticks.iter()
+            .filter(|(_, ticks)| ticks.added > since)
+            .map(|(entity, _)| *entity)
+            .collect()
+    }
+
+    /// Entities whose `T` was added or modified after `since`.
+    pub fn changed<T: Component>(&self, since: Tick) -> Vec<Entity> {
+        self.tracker::<T>().ticks.iter()
+            .filter(|(_, ticks)| ticks.changed > since)
+            .map(|(entity, _)| *entity)
+            .collect()
+    }
+
+    /// Entities that lost `T` (or were despawned) after `since`.
+    pub fn removed<T: Component>(&self, since: Tick) -> Vec<Entity> {
+        self.tracker::<T>().removed.iter()
+            .filter(|(_, removed_at)| **removed_at > since)
+            .map(|(entity, _)| *entity)
+            .collect()
+    }
+
+    pub fn is_added<T: Component>(&self, entity: Entity, since: Tick) -> bool {
+        self.ticks::<T>(entity).is_some_and(|ticks| ticks.added > since)
+    }
+
+    pub fn is_changed<T: Component>(&self, entity: Entity, since: Tick) -> bool {
+        self.ticks::<T>(entity).is_some_and(|ticks| ticks.changed > since)
+    }
+
+    fn tracker<T: Component>(&self) -> &TrackedComponent {
+        self.trackers.get(&TypeId::of::<T>())
+            .unwrap_or_else(|| panic!("Component changes are not tracked: {} (use App::track_changes)", type_name::<T>()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hecs::World;
+
+    use super::*;
+    use crate::core::context::Context;
+    use crate::core::schedule::{Schedule, Stage, System};
+    use crate::core::state::{GameState, StateMachine};
+    use crate::prelude::AssetServer;
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Health(i32);
+
+    // Entities the reader saw as changed on each of its runs
+    #[derive(Default)]
+    struct Seen(Vec<Vec<Entity>>);
+
+    #[derive(Default)]
+    struct Target(Option<Entity>);
+
+    fn writer_system(ctx: &mut Context) {
+        if let Some(entity) = ctx.resource_mut::<Target>().0.take() {
+            ctx.world.get::<&mut Health>(entity).unwrap().0 -= 1;
+            ctx.mark_changed::<Health>(entity);
+        }
+    }
+
+    fn reader_system(ctx: &mut Context) {
+        let mut changed = ctx.changed::<Health>();
+        changed.sort();
+        ctx.resource_mut::<Seen>().0.push(changed);
+    }
+
+    fn setup() -> (Schedule, Context) {
+        let mut ctx = Context::new(World::new(), AssetServer::new());
+        let mut change_ticks = ChangeTicks::new();
+        change_ticks.track::<Health>();
+        ctx.insert_resource(change_ticks);
+        ctx.insert_resource(StateMachine::new(GameState::Playing));
+        ctx.insert_resource(Seen::default());
+        ctx.insert_resource(Target::default());
+
+        let mut schedule = Schedule::new();
+        schedule.add_system(Stage::Update, System::new(writer_system, vec![GameState::Playing]));
+        schedule.add_system(Stage::Update, System::new(reader_system, vec![GameState::Playing]));
+        schedule.build().unwrap();
+
+        (schedule, ctx)
+    }
+
+    #[test]
+    fn changed_reports_a_single_mutation() {
+        let (mut schedule, mut ctx) = setup();
+        let mut entities: Vec<Entity> = (0..3).map(|i| ctx.spawn((Health(i),))).collect();
+        entities.sort();
+
+        // First run: every spawned entity counts as changed
+        schedule.run_stage(Stage::Update, &mut ctx);
+
+        ctx.resource_mut::<Target>().0 = Some(entities[1]);
+        schedule.run_stage(Stage::Update, &mut ctx);
+
+        // Nothing written since the last run
+        schedule.run_stage(Stage::Update, &mut ctx);
+
+        assert_eq!(ctx.resource::<Seen>().0, vec![entities.clone(), vec![entities[1]], vec![]]);
+    }
+
+    #[test]
+    fn writer_does_not_see_its_own_writes() {
+        let (mut schedule, mut ctx) = setup();
+        let entity = ctx.spawn((Health(10),));
+
+        // Runs after the writer: sees the write once, then nothing
+        schedule.run_stage(Stage::Update, &mut ctx);
+        ctx.resource_mut::<Target>().0 = Some(entity);
+        schedule.run_stage(Stage::Update, &mut ctx);
+
+        let writer_last_run = ctx.resource::<ChangeTicks>().ticks::<Health>(entity).unwrap().changed;
+        assert!(!ctx.resource::<ChangeTicks>().is_changed::<Health>(entity, writer_last_run));
+        assert_eq!(ctx.resource::<Seen>().0, vec![vec![entity], vec![entity]]);
+    }
+
+    #[test]
+    fn despawn_is_reported_as_removed() {
+        let (_, mut ctx) = setup();
+        let entity = ctx.spawn((Health(1),));
+        let since = ctx.resource::<ChangeTicks>().current_tick();
+        ctx.resource_mut::<ChangeTicks>().advance();
+
+        ctx.despawn(entity).unwrap();
+
+        let change_ticks = ctx.resource::<ChangeTicks>();
+        assert_eq!(change_ticks.removed::<Health>(since), vec![entity]);
+        assert!(change_ticks.changed::<Health>(0).is_empty());
+    }
+}
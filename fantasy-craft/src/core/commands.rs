@@ -0,0 +1,98 @@
+use hecs::{Bundle, Component, DynamicBundle, Entity, World};
+use macroquad::logging::warn;
+
+use crate::core::context::Context;
+
+type CommandFn = Box<dyn FnOnce(&mut Context) + Send>;
+
+/// Queue of world mutations recorded while iterating and applied at the end of the current stage.
+///
+/// Systems usually split the `Context` borrow to use it inside a query:
+///
+/// ```ignore
+/// let (world, resources) = (&ctx.world, &mut ctx.resources);
+/// let commands = resources.get_mut::<Commands>().expect("Commands resource is missing");
+///
+/// for (entity, _) in world.query::<&SplashScreenTag>().iter() {
+///     commands.despawn(entity);
+/// }
+/// ```
+#[derive(Default)]
+pub struct Commands {
+    queue: Vec<CommandFn>,
+    scene_requests: Vec<String>
+}
+
+impl Commands {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reserves the entity id right away, its components are added when the queue is flushed.
+    pub fn spawn<B: DynamicBundle + Send + 'static>(&mut self, world: &World, bundle: B) -> Entity {
+        let entity = world.reserve_entity();
+        self.insert(entity, bundle);
+        entity
+    }
+
+    pub fn despawn(&mut self, entity: Entity) {
+        self.queue.push(Box::new(move |ctx: &mut Context| {
+            if ctx.despawn(entity).is_err() {
+                warn!("Commands: Cannot despawn {:?}, it no longer exists", entity);
+            }
+        }));
+    }
+
+    pub fn insert<B: DynamicBundle + Send + 'static>(&mut self, entity: Entity, bundle: B) {
+        self.queue.push(Box::new(move |ctx: &mut Context| {
+            if ctx.insert(entity, bundle).is_err() {
+                warn!("Commands: Cannot insert components on {:?}, it no longer exists", entity);
+            }
+        }));
+    }
+
+    pub fn insert_one<C: Component>(&mut self, entity: Entity, component: C) {
+        self.insert(entity, (component,));
+    }
+
+    pub fn remove<B: Bundle + Send + 'static>(&mut self, entity: Entity) {
+        self.queue.push(Box::new(move |ctx: &mut Context| {
+            if ctx.remove::<B>(entity).is_err() {
+                warn!("Commands: Cannot remove {} from {:?}", std::any::type_name::<B>(), entity);
+            }
+        }));
+    }
+
+    pub fn remove_one<C: Component>(&mut self, entity: Entity) {
+        self.remove::<(C,)>(entity);
+    }
+
+    /// Loads the scene file at `path` on top of the current world, like `SceneManager::load`.
+    /// Scene loading is asynchronous, so it happens at the end of the frame in `App::run`.
+    pub fn spawn_scene(&mut self, path: &str) {
+        self.scene_requests.push(path.to_string());
+    }
+
+    /// Applies every queued world mutation in order, running the component hooks.
+    /// Commands queued by the hooks themselves are applied in the same call.
+    pub fn apply(ctx: &mut Context) {
+        loop {
+            let queue = match ctx.get_resource_mut::<Commands>() {
+                Some(commands) if !commands.queue.is_empty() => std::mem::take(&mut commands.queue),
+                _ => return
+            };
+
+            for command in queue {
+                command(ctx);
+            }
+        }
+    }
+
+    pub fn take_scene_requests(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.scene_requests)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty() && self.scene_requests.is_empty()
+    }
+}
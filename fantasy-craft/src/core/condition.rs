@@ -0,0 +1,170 @@
+use std::any::Any;
+use std::ops::Not;
+
+use crate::core::app::Headless;
+use crate::core::context::Context;
+use crate::core::event::{EventBus, EventReader};
+use crate::core::state::{StateMachine, States};
+use crate::input::manager::InputManager;
+
+type ConditionFn = Box<dyn FnMut(&Context) -> bool>;
+
+/// A predicate evaluated by `Schedule::run_stage` right before running a system.
+/// Conditions can be combined with `and`, `or` and `!`.
+pub struct RunCondition(ConditionFn);
+
+impl RunCondition {
+    pub fn new<F>(condition: F) -> Self
+    where
+        F: FnMut(&Context) -> bool + 'static
+    {
+        Self(Box::new(condition))
+    }
+
+    pub fn evaluate(&mut self, ctx: &Context) -> bool {
+        (self.0)(ctx)
+    }
+
+    /// Both conditions must hold. Both are evaluated, so stateful conditions keep their state up to date.
+    pub fn and(mut self, mut other: RunCondition) -> Self {
+        Self::new(move |ctx| self.evaluate(ctx) & other.evaluate(ctx))
+    }
+
+    /// One of the conditions must hold. Both are evaluated, like `and`.
+    pub fn or(mut self, mut other: RunCondition) -> Self {
+        Self::new(move |ctx| self.evaluate(ctx) | other.evaluate(ctx))
+    }
+}
+
+impl Not for RunCondition {
+    type Output = RunCondition;
+
+    fn not(mut self) -> Self::Output {
+        Self::new(move |ctx| !self.evaluate(ctx))
+    }
+}
+
+/// True while a resource of type `T` is present in the `Context`.
+pub fn resource_exists<T: Any + 'static>() -> RunCondition {
+    RunCondition::new(|ctx| ctx.get_resource::<T>().is_some())
+}
+
+/// True when at least one event of type `E` was sent since the condition was last evaluated.
+pub fn on_event<E: 'static + Send + Sync>() -> RunCondition {
+    let mut reader = EventReader::<E>::new();
+
+    RunCondition::new(move |ctx| {
+        ctx.get_resource::<EventBus>()
+            .is_some_and(|event_bus| reader.read(event_bus).next().is_some())
+    })
+}
+
+/// True once every `seconds`, based on the `DeltaTime` of the stage the system runs in.
+pub fn every_seconds(seconds: f32) -> RunCondition {
+    let mut elapsed = 0.0;
+
+    RunCondition::new(move |ctx| {
+        elapsed += ctx.dt();
+
+        if elapsed >= seconds {
+            elapsed -= seconds;
+            true
+        } else {
+            false
+        }
+    })
+}
+
+/// False while the `App` runs headless (`App::step`), for systems that read the mouse
+/// and keyboard, draw or play sounds through macroquad, which needs a window.
+pub fn has_window() -> RunCondition {
+    RunCondition::new(|ctx| ctx.get_resource::<Headless>().is_none())
+}
+
+/// True on the frame the input action bound in the `InputManager` is pressed.
+/// Always false in headless mode.
+pub fn action_just_pressed(action: &str) -> RunCondition {
+    let action = action.to_string();
+
+    RunCondition::new(move |ctx| {
+        ctx.get_resource::<Headless>().is_none() && ctx.get_resource::<InputManager>()
+            .is_some_and(|input| input.is_action_just_pressed(&action))
+    })
+}
+
+/// True while the state machine of `S` is in `state`.
+pub fn in_state<S: States>(state: S) -> RunCondition {
+    in_any_state(vec![state])
+}
+
+/// True while the state machine of `S` is in one of `states`.
+/// False when `S` has no registered state machine.
+pub fn in_any_state<S: States>(states: Vec<S>) -> RunCondition {
+    RunCondition::new(move |ctx| {
+        ctx.get_resource::<StateMachine<S>>()
+            .is_some_and(|machine| states.contains(&machine.current()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use hecs::World;
+
+    use super::*;
+    use crate::core::schedule::{Schedule, Stage, System};
+    use crate::core::state::GameState;
+    use crate::core::time::DeltaTime;
+    use crate::prelude::AssetServer;
+
+    struct Enabled;
+
+    #[derive(Default)]
+    struct Runs(u32);
+
+    fn count_system(ctx: &mut Context) {
+        ctx.resource_mut::<Runs>().0 += 1;
+    }
+
+    fn setup(system: System) -> (Schedule, Context) {
+        let mut ctx = Context::new(World::new(), AssetServer::new());
+        ctx.insert_resource(StateMachine::new(GameState::Playing));
+        ctx.insert_resource(DeltaTime(0.5));
+        ctx.insert_resource(Runs::default());
+
+        let mut schedule = Schedule::new();
+        schedule.add_system(Stage::Update, system);
+        schedule.build().unwrap();
+
+        (schedule, ctx)
+    }
+
+    #[test]
+    fn timer_after_a_false_condition_keeps_counting() {
+        let (mut schedule, mut ctx) = setup(System::new(count_system, vec![GameState::Playing])
+            .run_if(resource_exists::<Enabled>())
+            .run_if(every_seconds(1.0)));
+
+        // The first 0.5s are counted while the first condition is false
+        schedule.run_stage(Stage::Update, &mut ctx);
+        assert_eq!(ctx.resource::<Runs>().0, 0);
+
+        ctx.insert_resource(Enabled);
+        schedule.run_stage(Stage::Update, &mut ctx);
+        assert_eq!(ctx.resource::<Runs>().0, 1);
+
+        schedule.run_stage(Stage::Update, &mut ctx);
+        assert_eq!(ctx.resource::<Runs>().0, 1);
+    }
+
+    #[test]
+    fn combined_conditions_evaluate_both_sides() {
+        let (mut schedule, mut ctx) = setup(System::new(count_system, vec![GameState::Playing])
+            .run_if(resource_exists::<Enabled>().and(every_seconds(1.0))));
+
+        schedule.run_stage(Stage::Update, &mut ctx);
+        ctx.insert_resource(Enabled);
+        schedule.run_stage(Stage::Update, &mut ctx);
+
+        assert_eq!(ctx.resource::<Runs>().0, 1);
+    }
+}
@@ -1,14 +1,17 @@
-use std::any::Any;
+use std::any::{Any, TypeId};
 
-use hecs::World;
+use hecs::{Bundle, Component, ComponentError, DynamicBundle, Entity, NoSuchEntity, World};
 
-use crate::{core::{resource::ResourceMap, time::DeltaTime}, prelude::{AssetServer, GameState}};
+use macroquad::logging::warn;
+
+use crate::{core::{app::AppExit, change_detection::{ChangeTicks, Tick}, event::EventBus, hooks, resource::ResourceMap, screen::ScreenInfo, state::{StateMachine, StateTransition, States}, time::{DeltaTime, Time}}, prelude::AssetServer};
 
 pub struct Context {
     pub world: World,
     pub asset_server: AssetServer,
-    pub game_state: GameState,
-    pub resources: ResourceMap
+    pub resources: ResourceMap,
+    /// Tick at which the running system last ran, see `Context::changed`.
+    pub(crate) last_run: Tick
 }
 
 impl Context {
@@ -18,8 +21,94 @@ impl Context {
         Self {
             world,
             asset_server,
-            game_state: GameState::Playing,
-            resources
+            resources,
+            last_run: 0
+        }
+    }
+
+    /// Current value of the state machine of `S`.
+    /// Panics if `S` was not registered with `App::add_state`.
+    pub fn state<S: States>(&self) -> S {
+        self.resource::<StateMachine<S>>().current()
+    }
+
+    /// Switches to `state` at the end of the frame, running `OnExit` / `OnEnter`.
+    pub fn request_state<S: States>(&mut self, state: S) {
+        self.resource_mut::<StateMachine<S>>().queue(StateTransition::Set(state));
+    }
+
+    /// Enters `state` on top of the current one at the end of the frame (e.g. a pause screen).
+    pub fn push_state<S: States>(&mut self, state: S) {
+        self.resource_mut::<StateMachine<S>>().queue(StateTransition::Push(state));
+    }
+
+    /// Leaves the current state of `S` at the end of the frame and resumes the previous one.
+    pub fn pop_state<S: States>(&mut self) {
+        self.resource_mut::<StateMachine<S>>().queue(StateTransition::Pop);
+    }
+
+    /// Spawns an entity and runs the `on_add` hooks of its components.
+    pub fn spawn(&mut self, bundle: impl DynamicBundle) -> Entity {
+        let types = bundle.with_ids(|ids| ids.to_vec());
+        let entity = self.world.spawn(bundle);
+        self.stamp_inserted(entity, &types);
+        hooks::run_add_hooks(self, entity, &[]);
+        entity
+    }
+
+    /// Inserts components, running `on_add` for the ones `entity` did not have yet.
+    pub fn insert(&mut self, entity: Entity, bundle: impl DynamicBundle) -> Result<(), NoSuchEntity> {
+        let types = bundle.with_ids(|ids| ids.to_vec());
+        let before = hooks::hooked_components_of(self, entity);
+        self.world.insert(entity, bundle)?;
+        self.stamp_inserted(entity, &types);
+        hooks::run_add_hooks(self, entity, &before);
+        Ok(())
+    }
+
+    pub fn insert_one<C: Component>(&mut self, entity: Entity, component: C) -> Result<(), NoSuchEntity> {
+        self.insert(entity, (component,))
+    }
+
+    /// Runs `on_remove` for the hooked components of `B`, then removes them.
+    pub fn remove<B: Bundle + 'static>(&mut self, entity: Entity) -> Result<B, ComponentError> {
+        let removed: Vec<TypeId> = B::with_static_ids(|ids| hooks::hooked_components_of(self, entity)
+            .into_iter()
+            .filter(|type_id| ids.contains(type_id))
+            .collect());
+
+        hooks::run_remove_hooks(self, entity, &removed);
+        let bundle = self.world.remove::<B>(entity)?;
+
+        if let Some(change_ticks) = self.get_resource_mut::<ChangeTicks>() {
+            B::with_static_ids(|ids| for type_id in ids {
+                change_ticks.stamp_removed(*type_id, entity);
+            });
+        }
+        Ok(bundle)
+    }
+
+    pub fn remove_one<C: Component>(&mut self, entity: Entity) -> Result<C, ComponentError> {
+        self.remove::<(C,)>(entity).map(|(component,)| component)
+    }
+
+    /// Runs `on_remove` for every hooked component of `entity`, then despawns it.
+    pub fn despawn(&mut self, entity: Entity) -> Result<(), NoSuchEntity> {
+        let removed = hooks::hooked_components_of(self, entity);
+        hooks::run_remove_hooks(self, entity, &removed);
+        self.world.despawn(entity)?;
+
+        if let Some(change_ticks) = self.get_resource_mut::<ChangeTicks>() {
+            change_ticks.stamp_despawned(entity);
+        }
+        Ok(())
+    }
+
+    fn stamp_inserted(&mut self, entity: Entity, types: &[TypeId]) {
+        if let Some(change_ticks) = self.get_resource_mut::<ChangeTicks>() {
+            for type_id in types {
+                change_ticks.stamp_inserted(*type_id, entity);
+            }
         }
     }
 
@@ -53,7 +142,68 @@ impl Context {
         self.resources.get_mut::<T>()
     }
 
+    /// Window size, DPI scale and focus of the current frame.
+    pub fn screen(&self) -> ScreenInfo {
+        self.get_resource::<ScreenInfo>()
+            .copied()
+            .unwrap_or_else(ScreenInfo::current)
+    }
+
+    /// Asks the `App` to shut down at the end of the current frame.
+    pub fn exit(&mut self) {
+        match self.get_resource_mut::<EventBus>() {
+            Some(event_bus) => event_bus.send(AppExit),
+            None => warn!("Context: Cannot exit, the EventBus resource is missing")
+        }
+    }
+
+    /// Scaled delta of the running stage, zero while the game is paused.
     pub fn dt(&self) -> f32 {
         self.resource::<DeltaTime>().0
     }
+
+    /// Tick at which the running system last ran, 0 on its first run.
+    pub fn last_run_tick(&self) -> Tick {
+        self.last_run
+    }
+
+    /// Entities that received `T` since the running system last ran.
+    /// Panics if `T` is not tracked, see `App::track_changes`.
+    pub fn added<T: Component>(&self) -> Vec<Entity> {
+        self.resource::<ChangeTicks>().added::<T>(self.last_run)
+    }
+
+    /// Entities whose `T` was added or modified since the running system last ran.
+    /// Panics if `T` is not tracked, see `App::track_changes`.
+    pub fn changed<T: Component>(&self) -> Vec<Entity> {
+        self.resource::<ChangeTicks>().changed::<T>(self.last_run)
+    }
+
+    /// Entities that lost `T`, or were despawned, since the running system last ran.
+    /// Panics if `T` is not tracked, see `App::track_changes`.
+    pub fn removed<T: Component>(&self) -> Vec<Entity> {
+        self.resource::<ChangeTicks>().removed::<T>(self.last_run)
+    }
+
+    pub fn is_added<T: Component>(&self, entity: Entity) -> bool {
+        self.resource::<ChangeTicks>().is_added::<T>(entity, self.last_run)
+    }
+
+    pub fn is_changed<T: Component>(&self, entity: Entity) -> bool {
+        self.resource::<ChangeTicks>().is_changed::<T>(entity, self.last_run)
+    }
+
+    /// Records that `entity`'s `T` was modified in place, for `ctx.changed::<T>()`.
+    /// Insertions and removals made through `Context` are recorded on their own.
+    pub fn mark_changed<T: Component>(&mut self, entity: Entity) {
+        if let Some(change_ticks) = self.get_resource_mut::<ChangeTicks>() {
+            change_ticks.mark_changed::<T>(entity);
+        }
+    }
+
+    /// Real frame time, for GUI and input systems that must keep running during slow-motion or pause.
+    pub fn unscaled_dt(&self) -> f32 {
+        self.get_resource::<Time>()
+            .map_or_else(|| self.dt(), |time| time.unscaled_delta())
+    }
 }
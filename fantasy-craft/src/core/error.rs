@@ -0,0 +1,124 @@
+use std::fmt;
+
+use macroquad::prelude::*;
+
+use crate::core::screen::ScreenInfo;
+
+/// Errors raised while loading assets, scenes and components.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EngineError {
+    /// A file could not be read or downloaded.
+    Io { path: String, message: String },
+    /// A file was read but its content is invalid.
+    Parse { path: String, message: String },
+    /// An asset referenced by id does not exist in the `AssetServer`.
+    MissingAsset { kind: String, name: String },
+    /// The scene data of a component is invalid, or the component could not be inserted.
+    ComponentData { entity: String, component: String, message: String }
+}
+
+impl EngineError {
+    pub fn io(path: &str, error: impl fmt::Display) -> Self {
+        Self::Io { path: path.to_string(), message: error.to_string() }
+    }
+
+    pub fn parse(path: &str, error: impl fmt::Display) -> Self {
+        Self::Parse { path: path.to_string(), message: error.to_string() }
+    }
+
+    pub fn missing_asset(kind: &str, name: &str) -> Self {
+        Self::MissingAsset { kind: kind.to_string(), name: name.to_string() }
+    }
+
+    pub fn component_data(component: &str, error: impl fmt::Display) -> Self {
+        Self::ComponentData { entity: String::new(), component: component.to_string(), message: error.to_string() }
+    }
+
+    /// Attaches the scene id of the entity being loaded to a `ComponentData` error.
+    pub fn with_entity(self, entity_id: &str) -> Self {
+        match self {
+            Self::ComponentData { component, message, .. } => Self::ComponentData {
+                entity: entity_id.to_string(),
+                component,
+                message
+            },
+            other => other
+        }
+    }
+}
+
+impl fmt::Display for EngineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io { path, message } => write!(f, "Cannot read '{}': {}", path, message),
+            Self::Parse { path, message } => write!(f, "Cannot parse '{}': {}", path, message),
+            Self::MissingAsset { kind, name } => write!(f, "Missing {} '{}'", kind, name),
+            Self::ComponentData { entity, component, message } if entity.is_empty() => {
+                write!(f, "Invalid '{}' component: {}", component, message)
+            },
+            Self::ComponentData { entity, component, message } => {
+                write!(f, "Invalid '{}' component on entity '{}': {}", component, entity, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for EngineError {}
+
+/// Errors collected while the game runs. The `App` draws them on top of the game
+/// instead of aborting, so a typo in a scene file does not take the whole game down.
+#[derive(Debug, Default)]
+pub struct ErrorReport {
+    errors: Vec<EngineError>
+}
+
+impl ErrorReport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, error: EngineError) {
+        error!("{}", error);
+        self.errors.push(error);
+    }
+
+    pub fn extend(&mut self, errors: impl IntoIterator<Item = EngineError>) {
+        for error in errors {
+            self.push(error);
+        }
+    }
+
+    pub fn errors(&self) -> &[EngineError] {
+        &self.errors
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.errors.clear();
+    }
+
+    /// Draws the most recent errors at the bottom of the screen.
+    pub fn draw(&self, screen: &ScreenInfo) {
+        const MAX_LINES: usize = 8;
+        const FONT_SIZE: f32 = 18.0;
+        const LINE_HEIGHT: f32 = 20.0;
+
+        if self.errors.is_empty() {
+            return;
+        }
+
+        let shown = self.errors.len().min(MAX_LINES);
+        let height = (shown + 1) as f32 * LINE_HEIGHT + 10.0;
+        let y = screen.height - height;
+
+        draw_rectangle(0.0, y, screen.width, height, Color::new(0.3, 0.0, 0.0, 0.85));
+        draw_text(&format!("{} error(s):", self.errors.len()), 8.0, y + LINE_HEIGHT, FONT_SIZE, WHITE);
+
+        for (i, error) in self.errors.iter().rev().take(shown).enumerate() {
+            draw_text(&error.to_string(), 8.0, y + (i + 2) as f32 * LINE_HEIGHT, FONT_SIZE, Color::new(1.0, 0.8, 0.8, 1.0));
+        }
+    }
+}
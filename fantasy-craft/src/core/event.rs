@@ -1,24 +1,53 @@
 use std::any::{Any, TypeId};
 use std::collections::HashMap;
+use std::marker::PhantomData;
 
 // --- Internal Trait ---
 // Allows us to treat generic EventQueues as dynamic objects
 // so we can store them in a HashMap.
 trait EventQueueTrait: Any + Send + Sync {
+    fn update(&mut self);
+    fn sent_count(&self) -> usize;
     fn clear(&mut self);
     fn as_any(&self) -> &dyn Any;
     fn as_any_mut(&mut self) -> &mut dyn Any;
 }
 
 // --- Generic Queue ---
-// Holds the actual events of type E.
+// Double buffered: events sent during this update land in `current`,
+// the ones from the previous update stay readable in `previous`.
+// Every event gets an increasing id so readers can keep a cursor.
 struct EventQueue<E> {
-    events: Vec<E>
+    previous: Vec<E>,
+    current: Vec<E>,
+    // Id of the first event stored in `previous`
+    start_id: usize,
+    // Id the next sent event will get
+    event_count: usize
+}
+
+impl<E> EventQueue<E> {
+    fn events_since(&self, last_id: usize) -> impl Iterator<Item = &E> {
+        // Events older than `start_id` are gone, a late reader just skips them
+        let skip = last_id.saturating_sub(self.start_id);
+        self.previous.iter().chain(self.current.iter()).skip(skip)
+    }
 }
 
 impl<E: 'static + Send + Sync> EventQueueTrait for EventQueue<E> {
+    fn update(&mut self) {
+        self.start_id += self.previous.len();
+        self.previous = std::mem::take(&mut self.current);
+    }
+
+    fn sent_count(&self) -> usize {
+        self.current.len()
+    }
+
     fn clear(&mut self) {
-        self.events.clear();
+        self.start_id = self.event_count;
+        self.previous.clear();
+        self.current.clear();
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -33,7 +62,10 @@ impl<E: 'static + Send + Sync> EventQueueTrait for EventQueue<E> {
 impl<E> Default for EventQueue<E> {
     fn default() -> Self {
         Self {
-            events: Vec::new()
+            previous: Vec::new(),
+            current: Vec::new(),
+            start_id: 0,
+            event_count: 0
         }
     }
 }
@@ -49,7 +81,7 @@ impl EventBus {
         Self::default()
     }
 
-    /// Pushes an event into the queue for the current frame.
+    /// Pushes an event into the queue for the current update.
     pub fn send<E: 'static + Send + Sync>(&mut self, event: E) {
         let type_id = TypeId::of::<E>();
 
@@ -60,28 +92,166 @@ impl EventBus {
 
         // Downcast to the concrete type and push
         if let Some(q) = queue.as_any_mut().downcast_mut::<EventQueue<E>>() {
-            q.events.push(event);
+            q.current.push(event);
+            q.event_count += 1;
         }
     }
 
-    /// Returns an iterator over events of type E sent this frame.
+    /// Returns an iterator over every buffered event of type E (this update and the previous one).
+    /// Systems that must see each event exactly once should use an `EventReader` instead.
     pub fn read<E: 'static + Send + Sync>(&self) -> impl Iterator<Item = &E> {
-        let type_id = TypeId::of::<E>();
+        self.queue::<E>()
+            .map(|q| q.events_since(0))
+            .into_iter()
+            .flatten()
+    }
 
-        if let Some(queue) = self.queues.get(&type_id) {
-            if let Some(q) = queue.as_any().downcast_ref::<EventQueue<E>>() {
-                // We return an iterator over the slice
-                return Some(q.events.iter()).into_iter().flatten();
-            }
+    /// Swaps the buffers: events of the previous update are dropped, the current ones become the previous ones.
+    /// Called by the `App` at the end of every frame.
+    pub fn update(&mut self) {
+        for queue in self.queues.values_mut() {
+            queue.update();
         }
+    }
 
-        // If queue doesn't exist, return an empty iterator
-        None.into_iter().flatten()
+    /// Number of events of every type sent during the current update.
+    pub fn sent_count(&self) -> usize {
+        self.queues.values().map(|queue| queue.sent_count()).sum()
     }
 
+    /// Drops every buffered event, readers will not see them.
     pub fn clear(&mut self) {
         for queue in self.queues.values_mut() {
             queue.clear();
         }
     }
+
+    fn queue<E: 'static + Send + Sync>(&self) -> Option<&EventQueue<E>> {
+        self.queues
+            .get(&TypeId::of::<E>())
+            .and_then(|queue| queue.as_any().downcast_ref::<EventQueue<E>>())
+    }
+}
+
+/// Cursor into the events of type E.
+/// Each reader sees every event once, as long as it runs at least once per update cycle.
+/// Usually kept as a `Local<EventReader<E>>`, see `System::with_local`.
+pub struct EventReader<E> {
+    last_id: usize,
+    _marker: PhantomData<fn() -> E>
+}
+
+impl<E: 'static + Send + Sync> EventReader<E> {
+    pub fn new() -> Self {
+        Self {
+            last_id: 0,
+            _marker: PhantomData
+        }
+    }
+
+    /// Returns the events sent since the last call and moves the cursor past them.
+    pub fn read<'a>(&mut self, event_bus: &'a EventBus) -> impl Iterator<Item = &'a E> + use<'a, E> {
+        let queue = event_bus.queue::<E>();
+        let last_id = self.last_id;
+
+        if let Some(q) = queue {
+            self.last_id = q.event_count;
+        }
+
+        queue
+            .map(move |q| q.events_since(last_id))
+            .into_iter()
+            .flatten()
+    }
+
+    /// Moves the cursor past every buffered event without reading them.
+    pub fn clear(&mut self, event_bus: &EventBus) {
+        if let Some(q) = event_bus.queue::<E>() {
+            self.last_id = q.event_count;
+        }
+    }
+}
+
+impl<E: 'static + Send + Sync> Default for EventReader<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read(reader: &mut EventReader<u32>, event_bus: &EventBus) -> Vec<u32> {
+        reader.read(event_bus).copied().collect()
+    }
+
+    #[test]
+    fn reader_sees_each_event_once_across_the_double_buffer() {
+        let mut event_bus = EventBus::new();
+        let mut reader = EventReader::<u32>::new();
+
+        event_bus.send(1u32);
+        event_bus.send(2u32);
+        assert_eq!(read(&mut reader, &event_bus), [1, 2]);
+
+        // 1 and 2 move to the previous buffer and are not read again
+        event_bus.update();
+        event_bus.send(3u32);
+        assert_eq!(read(&mut reader, &event_bus), [3]);
+        assert_eq!(read(&mut reader, &event_bus), Vec::<u32>::new());
+
+        // Sent after the reader ran: read on its next run, from the previous buffer
+        event_bus.send(4u32);
+        event_bus.update();
+        event_bus.send(5u32);
+        assert_eq!(read(&mut reader, &event_bus), [4, 5]);
+    }
+
+    #[test]
+    fn readers_keep_their_own_cursor() {
+        let mut event_bus = EventBus::new();
+        let mut early = EventReader::<u32>::new();
+        let mut late = EventReader::<u32>::new();
+
+        event_bus.send(1u32);
+        assert_eq!(read(&mut early, &event_bus), [1]);
+
+        event_bus.update();
+        event_bus.send(2u32);
+
+        assert_eq!(read(&mut late, &event_bus), [1, 2]);
+        assert_eq!(read(&mut early, &event_bus), [2]);
+    }
+
+    #[test]
+    fn events_older_than_two_updates_are_dropped() {
+        let mut event_bus = EventBus::new();
+        let mut reader = EventReader::<u32>::new();
+
+        event_bus.send(1u32);
+        event_bus.update();
+        event_bus.send(2u32);
+        event_bus.update();
+        event_bus.send(3u32);
+
+        assert_eq!(event_bus.read::<u32>().copied().collect::<Vec<_>>(), [2, 3]);
+        assert_eq!(read(&mut reader, &event_bus), [2, 3]);
+    }
+
+    #[test]
+    fn clear_skips_the_buffered_events() {
+        let mut event_bus = EventBus::new();
+        let mut reader = EventReader::<u32>::new();
+
+        event_bus.send(1u32);
+        reader.clear(&event_bus);
+        event_bus.send(2u32);
+        assert_eq!(read(&mut reader, &event_bus), [2]);
+
+        event_bus.send(3u32);
+        event_bus.clear();
+        event_bus.send(4u32);
+        assert_eq!(read(&mut reader, &event_bus), [4]);
+    }
 }
@@ -0,0 +1,95 @@
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use hecs::{Component, Entity, World};
+
+use crate::core::context::Context;
+
+/// Callback run with the entity whose component was added or is about to be removed.
+pub type ComponentHook = Arc<dyn Fn(&mut Context, Entity) + Send + Sync>;
+
+struct HookEntry {
+    has: fn(&World, Entity) -> bool,
+    on_add: Vec<ComponentHook>,
+    on_remove: Vec<ComponentHook>
+}
+
+fn has_component<T: Component>(world: &World, entity: Entity) -> bool {
+    world.satisfies::<&T>(entity).unwrap_or(false)
+}
+
+/// Resource holding the hooks registered with `App::on_add` and `App::on_remove`.
+///
+/// Hooks only fire for changes made through `Context` (`spawn`, `insert`, `insert_one`,
+/// `remove_one`, `despawn`), `Commands` and the scene loader, not for direct `ctx.world` calls.
+/// `on_add` runs right after the component was inserted, when the entity did not have it yet.
+/// `on_remove` runs right before the component is removed, so the hook can still read it.
+#[derive(Default)]
+pub struct ComponentHooks {
+    entries: HashMap<TypeId, HookEntry>
+}
+
+impl ComponentHooks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn on_add<T: Component>(&mut self, hook: impl Fn(&mut Context, Entity) + Send + Sync + 'static) {
+        self.entry::<T>().on_add.push(Arc::new(hook));
+    }
+
+    pub fn on_remove<T: Component>(&mut self, hook: impl Fn(&mut Context, Entity) + Send + Sync + 'static) {
+        self.entry::<T>().on_remove.push(Arc::new(hook));
+    }
+
+    fn entry<T: Component>(&mut self) -> &mut HookEntry {
+        self.entries.entry(TypeId::of::<T>()).or_insert_with(|| HookEntry {
+            has: has_component::<T>,
+            on_add: Vec::new(),
+            on_remove: Vec::new()
+        })
+    }
+}
+
+/// Hooked component types currently on `entity`.
+pub(crate) fn hooked_components_of(ctx: &Context, entity: Entity) -> Vec<TypeId> {
+    let Some(hooks) = ctx.get_resource::<ComponentHooks>() else {
+        return Vec::new();
+    };
+
+    hooks.entries.iter()
+        .filter(|(_, entry)| (entry.has)(&ctx.world, entity))
+        .map(|(type_id, _)| *type_id)
+        .collect()
+}
+
+/// Runs `on_add` for the hooked components `entity` has now but did not have in `before`.
+pub(crate) fn run_add_hooks(ctx: &mut Context, entity: Entity, before: &[TypeId]) {
+    let added: Vec<TypeId> = hooked_components_of(ctx, entity).into_iter()
+        .filter(|type_id| !before.contains(type_id))
+        .collect();
+
+    for hook in collect_hooks(ctx, &added, |entry| &entry.on_add) {
+        hook(ctx, entity);
+    }
+}
+
+/// Runs `on_remove` for `types`, which `entity` is about to lose.
+pub(crate) fn run_remove_hooks(ctx: &mut Context, entity: Entity, types: &[TypeId]) {
+    for hook in collect_hooks(ctx, types, |entry| &entry.on_remove) {
+        hook(ctx, entity);
+    }
+}
+
+// Hooks are cloned out of the resource since they need `&mut Context`
+fn collect_hooks(ctx: &Context, types: &[TypeId], hooks_of: fn(&HookEntry) -> &Vec<ComponentHook>) -> Vec<ComponentHook> {
+    let Some(hooks) = ctx.get_resource::<ComponentHooks>() else {
+        return Vec::new();
+    };
+
+    types.iter()
+        .filter_map(|type_id| hooks.entries.get(type_id))
+        .flat_map(|entry| hooks_of(entry).iter().cloned())
+        .collect()
+}
@@ -1,8 +1,17 @@
 pub mod app;
+pub mod condition;
 pub mod asset_server;
+pub mod commands;
+pub mod hooks;
+pub mod change_detection;
+pub mod rng;
+pub mod project;
+pub mod screen;
+pub mod error;
 pub mod context;
 pub mod plugins;
 pub mod schedule;
+pub mod state;
 pub mod resource;
 pub mod time;
 pub mod event;
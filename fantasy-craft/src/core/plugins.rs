@@ -1,17 +1,197 @@
-use crate::{audio::plugin::AudioPlugin, core::{app::App, event::EventBus, time::DeltaTime}, hierarchy::plugins::HierarchyPlugin, input::plugin::InputPlugin, prelude::{AnimationPlugin, Camera2dPlugin, GameState, GuiPlugin, PhysicsPlugin, Stage, System, TiledMapPlugin, collider_debug_render_system}, utils::plugins::UtilsPlugin};
+use std::any::{TypeId, type_name};
 
-pub trait Plugin {
+use crate::{audio::plugin::AudioPlugin, core::{app::App, change_detection::ChangeTicks, commands::Commands, event::EventBus, rng::Rng, time::{DeltaTime, FixedTime, Time}}, hierarchy::plugins::HierarchyPlugin, input::plugin::InputPlugin, timer::plugins::TimerPlugin, prelude::{AnimationPlugin, Camera2dPlugin, GameState, GuiPlugin, PhysicsPlugin, Stage, System, TiledMapPlugin, collider_debug_render_system, has_window}, utils::plugins::UtilsPlugin, scene::scene_manager::{PersistentLoader, SceneManager}};
+
+pub trait Plugin: 'static {
     fn build(&self, app: &mut App);
+
+    /// Name used in error messages, defaults to the type name without its module path.
+    fn name(&self) -> &'static str {
+        short_type_name(type_name::<Self>())
+    }
+
+    /// Plugins that must also be added to the `App`, in any order.
+    /// Checked once every plugin is built.
+    fn dependencies(&self) -> Vec<PluginId> {
+        Vec::new()
+    }
+
+    /// Unique plugins cause a panic when added twice, instead of registering their systems twice.
+    fn is_unique(&self) -> bool {
+        true
+    }
 }
 
-pub struct Default2dPlugin;
+fn short_type_name(name: &'static str) -> &'static str {
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+/// Identifies a plugin type, see `Plugin::dependencies`.
+#[derive(Debug, Clone, Copy)]
+pub struct PluginId {
+    type_id: TypeId,
+    name: &'static str
+}
+
+impl PluginId {
+    pub fn of<T: Plugin>() -> Self {
+        Self {
+            type_id: TypeId::of::<T>(),
+            name: short_type_name(type_name::<T>())
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl PartialEq for PluginId {
+    fn eq(&self, other: &Self) -> bool {
+        self.type_id == other.type_id
+    }
+}
+
+impl Eq for PluginId {}
+
+/// A set of plugins added together with `App::add_plugins`.
+pub trait PluginGroup {
+    fn build(self) -> PluginGroupBuilder;
+}
+
+struct PluginGroupEntry {
+    id: PluginId,
+    replaces: Vec<PluginId>,
+    plugin: Box<dyn Plugin>,
+    enabled: bool
+}
+
+/// Ordered list of the plugins of a group, which can be customised before being added:
+///
+/// ```ignore
+/// app.add_plugins(Default2dPlugin.build()
+///     .disable::<AudioPlugin>()
+///     .replace::<Camera2dPlugin>(MyCameraPlugin));
+/// ```
+#[derive(Default)]
+pub struct PluginGroupBuilder {
+    entries: Vec<PluginGroupEntry>
+}
+
+impl PluginGroupBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends `plugin`, or puts it in place of the plugin of the same type already in the group.
+    pub fn add_plugin<T: Plugin>(mut self, plugin: T) -> Self {
+        let id = PluginId::of::<T>();
+
+        match self.position(id) {
+            Some(index) => {
+                self.entries[index].plugin = Box::new(plugin);
+                self.entries[index].enabled = true;
+            },
+            None => self.entries.push(PluginGroupEntry {
+                id,
+                replaces: Vec::new(),
+                plugin: Box::new(plugin),
+                enabled: true
+            })
+        }
+
+        self
+    }
+
+    /// Puts `plugin` in the slot of `T`, keeping the build order.
+    /// Plugins depending on `T` accept the replacement instead.
+    pub fn replace<T: Plugin>(mut self, plugin: impl Plugin) -> Self {
+        let index = self.position(PluginId::of::<T>())
+            .unwrap_or_else(|| panic!("PluginGroup: Cannot replace '{}', it is not part of the group", short_type_name(type_name::<T>())));
+
+        let mut replaces = std::mem::take(&mut self.entries[index].replaces);
+        replaces.push(self.entries[index].id);
+
+        self.entries[index] = PluginGroupEntry {
+            id: plugin_id_of_val(&plugin),
+            replaces,
+            plugin: Box::new(plugin),
+            enabled: true
+        };
+        self
+    }
+
+    pub fn disable<T: Plugin>(mut self) -> Self {
+        if let Some(index) = self.position(PluginId::of::<T>()) {
+            self.entries[index].enabled = false;
+        }
+        self
+    }
+
+    pub fn enable<T: Plugin>(mut self) -> Self {
+        if let Some(index) = self.position(PluginId::of::<T>()) {
+            self.entries[index].enabled = true;
+        }
+        self
+    }
+
+    /// Adds every enabled plugin to `app`, in order.
+    pub fn finish(self, app: &mut App) {
+        for entry in self.entries {
+            if entry.enabled {
+                app.add_boxed_plugin(entry.id, entry.replaces, entry.plugin);
+            }
+        }
+    }
+
+    fn position(&self, id: PluginId) -> Option<usize> {
+        self.entries.iter().position(|entry| entry.id == id)
+    }
+}
 
-impl Plugin for Default2dPlugin {
+impl PluginGroup for PluginGroupBuilder {
+    fn build(self) -> PluginGroupBuilder {
+        self
+    }
+}
+
+fn plugin_id_of_val<T: Plugin>(_plugin: &T) -> PluginId {
+    PluginId::of::<T>()
+}
+
+/// Resources every other engine plugin relies on.
+pub struct CorePlugin;
+
+impl Plugin for CorePlugin {
     fn build(&self, app: &mut App) {
         app.context.insert_resource(EventBus::new());
+        app.context.insert_resource(Commands::new());
         app.context.insert_resource(DeltaTime(0.0));
+        app.context.insert_resource(Time::new());
+        app.context.insert_resource(FixedTime::default());
+        app.context.insert_resource(SceneManager::new());
 
-        app
+        app.scene_loader
+            .register("Persistent", Box::new(PersistentLoader));
+
+        // Plugins may have registered tracked components already
+        if app.context.get_resource::<ChangeTicks>().is_none() {
+            app.context.insert_resource(ChangeTicks::new());
+        }
+
+        // Keep the seed given with `App::with_seed`
+        if app.context.get_resource::<Rng>().is_none() {
+            app.context.insert_resource(Rng::from_entropy());
+        }
+    }
+}
+
+pub struct Default2dPlugin;
+
+impl PluginGroup for Default2dPlugin {
+    fn build(self) -> PluginGroupBuilder {
+        PluginGroupBuilder::new()
+            .add_plugin(CorePlugin)
             .add_plugin(UtilsPlugin)
             .add_plugin(PhysicsPlugin)
             .add_plugin(Camera2dPlugin)
@@ -20,7 +200,8 @@ impl Plugin for Default2dPlugin {
             .add_plugin(AnimationPlugin)
             .add_plugin(GuiPlugin)
             .add_plugin(AudioPlugin)
-            .add_plugin(InputPlugin);
+            .add_plugin(InputPlugin)
+            .add_plugin(TimerPlugin)
     }
 }
 
@@ -32,6 +213,90 @@ impl Plugin for DebugPlugin {
             .add_system(Stage::PostRender, System::new(
                 collider_debug_render_system,
                 vec![GameState::Playing]
-            ));
+            ).run_if(has_window()));
+    }
+
+    fn dependencies(&self) -> Vec<PluginId> {
+        vec![PluginId::of::<PhysicsPlugin>()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use macroquad::window::Conf;
+
+    use super::*;
+
+    struct CustomHierarchyPlugin;
+
+    impl Plugin for CustomHierarchyPlugin {
+        fn build(&self, _app: &mut App) {}
+    }
+
+    struct CustomCameraPlugin;
+
+    impl Plugin for CustomCameraPlugin {
+        fn build(&self, _app: &mut App) {}
+    }
+
+    #[test]
+    fn replacement_satisfies_the_dependencies_on_the_replaced_plugin() {
+        let mut app = App::new(Conf::default());
+        app.add_plugins(Default2dPlugin.build()
+            .replace::<HierarchyPlugin>(CustomHierarchyPlugin)
+            .replace::<CustomHierarchyPlugin>(CustomCameraPlugin));
+
+        // Camera2dPlugin and GuiPlugin depend on HierarchyPlugin
+        app.build_plugins();
+
+        assert!(app.is_plugin_added::<CustomCameraPlugin>());
+        assert!(app.is_plugin_added::<CustomHierarchyPlugin>());
+        assert!(app.is_plugin_added::<HierarchyPlugin>());
+    }
+
+    #[test]
+    #[should_panic(expected = "'Camera2dPlugin' requires 'HierarchyPlugin'")]
+    fn disabled_dependency_is_reported() {
+        let mut app = App::new(Conf::default());
+        app.add_plugins(Default2dPlugin.build().disable::<HierarchyPlugin>());
+
+        app.build_plugins();
+    }
+
+    mod editor {
+        use super::*;
+
+        pub struct OverlayPlugin;
+
+        impl Plugin for OverlayPlugin {
+            fn build(&self, _app: &mut App) {}
+
+            fn name(&self) -> &'static str {
+                "EditorOverlay"
+            }
+        }
+    }
+
+    mod hud {
+        use super::*;
+
+        pub struct OverlayPlugin;
+
+        impl Plugin for OverlayPlugin {
+            fn build(&self, _app: &mut App) {}
+        }
+    }
+
+    #[test]
+    fn disabling_a_plugin_only_skips_that_plugin() {
+        let mut app = App::new(Conf::default());
+        app.with_plugin_enabled("EditorOverlay", false)
+            .add_plugin(editor::OverlayPlugin)
+            .add_plugin(hud::OverlayPlugin);
+
+        app.build_plugins();
+
+        assert!(!app.is_plugin_added::<editor::OverlayPlugin>());
+        assert!(app.is_plugin_added::<hud::OverlayPlugin>());
     }
 }
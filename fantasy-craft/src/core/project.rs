@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+
+use macroquad::prelude::*;
+use serde::Deserialize;
+
+use crate::core::error::EngineError;
+use crate::gui::color_data::ColorData;
+
+/// Startup configuration read from a `project.json` file.
+///
+/// Every field is optional: missing values keep the engine defaults, and the
+/// `App::with_*` calls made in code take precedence over the file.
+#[derive(Deserialize, Debug, Default)]
+#[serde(default)]
+pub struct ProjectSettings {
+    pub window: WindowSettings,
+    pub clear_color: Option<ColorData>,
+    pub splash_screen: SplashScreenSettings,
+    pub assets_file: Option<String>,
+    pub start_scene: Option<String>,
+    pub bindings_file: Option<String>,
+    /// Plugin name (e.g. `"DebugPlugin"`) to enabled flag. Plugins not listed stay enabled.
+    pub plugins: HashMap<String, bool>
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct WindowSettings {
+    pub title: Option<String>,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub fullscreen: Option<bool>,
+    pub resizable: Option<bool>,
+    pub high_dpi: Option<bool>
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(default)]
+pub struct SplashScreenSettings {
+    pub enabled: Option<bool>,
+    pub logo: Option<String>,
+    pub background_color: Option<ColorData>
+}
+
+impl ProjectSettings {
+    pub fn from_json(path: &str, content: &str) -> Result<Self, EngineError> {
+        serde_json::from_str(content).map_err(|e| EngineError::parse(path, e))
+    }
+
+    /// Loads the settings through macroquad's file API, which also works on the web.
+    pub async fn load(path: &str) -> Result<Self, EngineError> {
+        let content = load_string(path).await.map_err(|e| EngineError::io(path, e))?;
+        Self::from_json(path, &content)
+    }
+
+    /// Reads the settings synchronously, for `window_conf` which runs before the window exists.
+    /// There is no file system on the web, so this always fails there.
+    pub fn load_sync(path: &str) -> Result<Self, EngineError> {
+        let content = std::fs::read_to_string(path).map_err(|e| EngineError::io(path, e))?;
+        Self::from_json(path, &content)
+    }
+
+    /// Builds the window configuration for `#[macroquad::main]`: `default`, then the `window` section
+    /// of `path`, then the values set in `overrides`, each one taking precedence over the previous.
+    ///
+    /// ```ignore
+    /// fn window_conf() -> Conf {
+    ///     // The file may change the size, the title is always the one from the code
+    ///     ProjectSettings::window_conf("resources/project.json", Conf::default(), WindowSettings {
+    ///         title: Some("Fantasy Craft".to_owned()),
+    ///         ..Default::default()
+    ///     })
+    /// }
+    /// ```
+    pub fn window_conf(path: &str, default: Conf, overrides: WindowSettings) -> Conf {
+        let conf = match Self::load_sync(path) {
+            Ok(settings) => settings.window.apply(default),
+            Err(e) => {
+                warn!("ProjectSettings: {}", e);
+                default
+            }
+        };
+
+        overrides.apply(conf)
+    }
+
+    pub fn clear_color(&self) -> Option<Color> {
+        self.clear_color.as_ref().map(to_color)
+    }
+}
+
+impl WindowSettings {
+    pub fn apply(&self, mut conf: Conf) -> Conf {
+        if let Some(title) = &self.title {
+            conf.window_title = title.clone();
+        }
+        if let Some(width) = self.width {
+            conf.window_width = width;
+        }
+        if let Some(height) = self.height {
+            conf.window_height = height;
+        }
+        if let Some(fullscreen) = self.fullscreen {
+            conf.fullscreen = fullscreen;
+        }
+        if let Some(resizable) = self.resizable {
+            conf.window_resizable = resizable;
+        }
+        if let Some(high_dpi) = self.high_dpi {
+            conf.high_dpi = high_dpi;
+        }
+
+        conf
+    }
+}
+
+impl SplashScreenSettings {
+    pub fn background_color(&self) -> Option<Color> {
+        self.background_color.as_ref().map(to_color)
+    }
+}
+
+fn to_color(data: &ColorData) -> Color {
+    Color::new(data.r, data.g, data.b, data.a)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn overrides() -> WindowSettings {
+        WindowSettings {
+            title: Some("From code".to_owned()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn code_overrides_take_precedence_over_the_file() {
+        let path = std::env::temp_dir().join("fantasy_craft_window_conf.json");
+        std::fs::write(&path, r#"{ "window": { "title": "From file", "width": 640 } }"#).unwrap();
+
+        let conf = ProjectSettings::window_conf(path.to_str().unwrap(), Conf {
+            window_width: 1920,
+            window_height: 1080,
+            ..Default::default()
+        }, overrides());
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(conf.window_title, "From code");
+        assert_eq!(conf.window_width, 640);
+        assert_eq!(conf.window_height, 1080);
+    }
+
+    #[test]
+    fn missing_file_keeps_the_default_and_the_overrides() {
+        let conf = ProjectSettings::window_conf("missing/project.json", Conf {
+            window_width: 1920,
+            ..Default::default()
+        }, overrides());
+
+        assert_eq!(conf.window_title, "From code");
+        assert_eq!(conf.window_width, 1920);
+    }
+}
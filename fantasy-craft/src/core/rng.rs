@@ -0,0 +1,217 @@
+use std::collections::HashMap;
+use std::ops::{Deref, DerefMut};
+
+use macroquad::miniquad::date;
+
+const PCG_MULTIPLIER: u64 = 6364136223846793005;
+
+/// PCG32 generator. Cheap, small state and the same sequence on every platform for a given seed.
+#[derive(Debug, Clone)]
+pub struct RngStream {
+    state: u64,
+    increment: u64
+}
+
+impl RngStream {
+    pub fn new(seed: u64, stream_id: u64) -> Self {
+        let mut stream = Self {
+            state: 0,
+            // The increment must be odd
+            increment: (stream_id << 1) | 1
+        };
+
+        stream.next_u32();
+        stream.state = stream.state.wrapping_add(seed);
+        stream.next_u32();
+        stream
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        let old_state = self.state;
+        self.state = old_state.wrapping_mul(PCG_MULTIPLIER).wrapping_add(self.increment);
+
+        let xorshifted = (((old_state >> 18) ^ old_state) >> 27) as u32;
+        let rotation = (old_state >> 59) as u32;
+        xorshifted.rotate_right(rotation)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        ((self.next_u32() as u64) << 32) | self.next_u32() as u64
+    }
+
+    /// Uniform float in [0.0, 1.0).
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u32() >> 8) as f32 / (1u32 << 24) as f32
+    }
+
+    /// Uniform float in [min, max).
+    pub fn range_f32(&mut self, min: f32, max: f32) -> f32 {
+        min + (max - min) * self.next_f32()
+    }
+
+    /// Uniform integer in [min, max). Returns `min` when the range is empty.
+    pub fn range_i32(&mut self, min: i32, max: i32) -> i32 {
+        if max <= min {
+            return min;
+        }
+
+        let span = (max as i64 - min as i64) as u64;
+        (min as i64 + (self.next_u64() % span) as i64) as i32
+    }
+
+    /// Uniform index in [min, max). Returns `min` when the range is empty.
+    pub fn range_usize(&mut self, min: usize, max: usize) -> usize {
+        if max <= min {
+            return min;
+        }
+
+        min + (self.next_u64() % (max - min) as u64) as usize
+    }
+
+    /// True with the given probability (0.0 - 1.0).
+    pub fn chance(&mut self, probability: f32) -> bool {
+        self.next_f32() < probability
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        if items.is_empty() {
+            return None;
+        }
+
+        Some(&items[self.range_usize(0, items.len())])
+    }
+
+    /// Fisher-Yates shuffle.
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.range_usize(0, i + 1);
+            items.swap(i, j);
+        }
+    }
+}
+
+/// Seedable random number generator resource.
+///
+/// Each subsystem should draw from its own named `stream`, so adding randomness to
+/// one feature does not shift the sequence seen by the others. The same seed always
+/// produces the same streams, which makes replays, tests and procedural content reproducible.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    seed: u64,
+    main: RngStream,
+    streams: HashMap<String, RngStream>
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            main: RngStream::new(seed, 0),
+            streams: HashMap::new()
+        }
+    }
+
+    /// Seeds from the clock, for games that do not need reproducible runs.
+    pub fn from_entropy() -> Self {
+        Self::new((date::now() * 1_000_000.0) as u64)
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Restarts the main stream and every named stream from `seed`.
+    pub fn reseed(&mut self, seed: u64) {
+        *self = Self::new(seed);
+    }
+
+    /// Returns the stream reserved to `name`, created on first use.
+    pub fn stream(&mut self, name: &str) -> &mut RngStream {
+        let seed = self.seed;
+
+        self.streams
+            .entry(name.to_string())
+            .or_insert_with(|| RngStream::new(seed, stream_id(name)))
+    }
+}
+
+impl Deref for Rng {
+    type Target = RngStream;
+
+    fn deref(&self) -> &Self::Target {
+        &self.main
+    }
+}
+
+impl DerefMut for Rng {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.main
+    }
+}
+
+// FNV-1a, stable across Rust versions unlike `DefaultHasher`
+fn stream_id(name: &str) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+
+    for byte in name.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn draw(stream: &mut RngStream) -> Vec<u32> {
+        (0..8).map(|_| stream.next_u32()).collect()
+    }
+
+    #[test]
+    fn same_seed_gives_the_same_streams() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+
+        assert_eq!(draw(&mut a), draw(&mut b));
+        assert_eq!(draw(a.stream("loot")), draw(b.stream("loot")));
+        assert_ne!(draw(&mut Rng::new(43)), draw(&mut Rng::new(42)));
+    }
+
+    #[test]
+    fn streams_do_not_shift_each_other() {
+        let mut a = Rng::new(7);
+        let mut b = Rng::new(7);
+
+        // `a` draws from other streams first, `b` does not
+        draw(&mut a);
+        draw(a.stream("weather"));
+
+        assert_eq!(draw(a.stream("npc")), draw(b.stream("npc")));
+        assert_ne!(draw(Rng::new(7).stream("npc")), draw(Rng::new(7).stream("weather")));
+    }
+
+    #[test]
+    fn reseed_restarts_every_stream() {
+        let mut rng = Rng::new(1);
+        let main = draw(&mut rng);
+        let named = draw(rng.stream("npc"));
+
+        rng.reseed(1);
+        assert_eq!(draw(&mut rng), main);
+        assert_eq!(draw(rng.stream("npc")), named);
+    }
+
+    #[test]
+    fn ranges_stay_in_bounds() {
+        let mut stream = RngStream::new(3, 0);
+
+        for _ in 0..1000 {
+            assert!((-5..5).contains(&stream.range_i32(-5, 5)));
+            assert!((0.0..1.0).contains(&stream.next_f32()));
+        }
+        assert_eq!(stream.range_i32(4, 4), 4);
+        assert_eq!(stream.choose::<u8>(&[]), None);
+    }
+}
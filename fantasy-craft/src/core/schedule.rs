@@ -1,53 +1,216 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+use std::ops::{Deref, DerefMut};
 
+use macroquad::logging::warn;
+
+use crate::core::change_detection::{ChangeTicks, Tick};
+use crate::core::commands::Commands;
+use crate::profiler::resources::{Profiler, StageProfile, SystemProfile};
+use crate::core::condition::{RunCondition, in_any_state};
 use crate::core::context::Context;
+use crate::core::state::{StateDriver, StateId, States};
 
-pub type SystemFn = fn(&mut Context);
+pub type SystemFn = Box<dyn FnMut(&mut Context)>;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Stage {
     StartUp,
     Update,
+    FixedUpdate,
     PostUpdate,
     Render,
     PostRender,
-    GuiRender
+    GuiRender,
+    /// Runs once when the app exits, after the last frame. See `AppExit`.
+    OnShutdown,
+    /// Runs once when the state is entered, with the new state already active.
+    /// Build it with `Stage::on_enter`.
+    OnEnter(StateId),
+    /// Runs once when the state is left, while it is still the active state.
+    /// Build it with `Stage::on_exit`.
+    OnExit(StateId)
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-pub enum GameState {
-    Menu,
-    Playing,
-    Paused,
-    Loading
+impl Stage {
+    pub fn on_enter<S: States>(state: S) -> Self {
+        Stage::OnEnter(StateId::of(state))
+    }
+
+    pub fn on_exit<S: States>(state: S) -> Self {
+        Stage::OnExit(StateId::of(state))
+    }
+}
+
+/// State owned by a single system and kept between two runs of that system.
+/// Created with `Default` when the system is built, see `System::with_local`.
+#[derive(Debug, Default)]
+pub struct Local<T>(pub T);
+
+impl<T> Deref for Local<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Local<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
 }
 
 pub struct System {
+    pub name: String,
     pub func: SystemFn,
-    pub active_states: Vec<GameState>
+    pub state_filter: RunCondition,
+    pub label: Option<String>,
+    pub before: Vec<String>,
+    pub after: Vec<String>,
+    pub conditions: Vec<RunCondition>,
+    /// `ChangeTicks` tick of the previous run, used by `Context::changed` and friends.
+    pub last_run: Tick
 }
 
 impl System {
-    pub fn new(func: SystemFn, active_states: Vec<GameState>) -> Self {
+    /// Accepts plain `fn(&mut Context)` systems as well as closures capturing their own state.
+    /// The system only runs while the state machine of `S` is in one of `active_states`.
+    pub fn new<F, S>(func: F, active_states: Vec<S>) -> Self
+    where
+        F: FnMut(&mut Context) + 'static,
+        S: States
+    {
+        Self::with_state_filter(func, in_any_state(active_states))
+    }
+
+    /// Builds a system that runs whatever the current states, including in apps that do not use
+    /// `GameState`. Meant for engine bookkeeping like timers; `run_if` still applies.
+    pub fn always<F>(func: F) -> Self
+    where
+        F: FnMut(&mut Context) + 'static
+    {
+        Self::with_state_filter(func, RunCondition::new(|_| true))
+    }
+
+    fn with_state_filter<F>(func: F, state_filter: RunCondition) -> Self
+    where
+        F: FnMut(&mut Context) + 'static
+    {
         Self {
-            func,
-            active_states
+            name: std::any::type_name::<F>().to_string(),
+            func: Box::new(func),
+            state_filter,
+            label: None,
+            before: Vec::new(),
+            after: Vec::new(),
+            conditions: Vec::new(),
+            last_run: 0
         }
     }
 
-    pub fn is_active(&self, current_state: GameState) -> bool {
-        self.active_states.contains(&current_state)
+    /// Builds a system that receives a `Local<T>` kept alive between its runs,
+    /// e.g. timers, caches or query scratch buffers.
+    pub fn with_local<T, F, S>(mut func: F, active_states: Vec<S>) -> Self
+    where
+        T: Default + 'static,
+        F: FnMut(&mut Context, &mut Local<T>) + 'static,
+        S: States
+    {
+        let mut local = Local::<T>::default();
+        let mut system = Self::new(move |ctx: &mut Context| func(ctx, &mut local), active_states);
+        system.name = std::any::type_name::<F>().to_string();
+        system
+    }
+
+    /// Names this system so that other systems of the same stage can order themselves around it.
+    pub fn label(mut self, label: &str) -> Self {
+        self.label = Some(label.to_string());
+        self
+    }
+
+    /// This system must run before the system labelled `label`.
+    pub fn before(mut self, label: &str) -> Self {
+        self.before.push(label.to_string());
+        self
+    }
+
+    /// This system must run after the system labelled `label`.
+    pub fn after(mut self, label: &str) -> Self {
+        self.after.push(label.to_string());
+        self
+    }
+
+    /// Only runs the system when `condition` holds, on top of its active states.
+    /// Several `run_if` calls must all hold. Every condition is evaluated each time the stage runs,
+    /// so stateful ones like `every_seconds` keep counting whatever the others return.
+    pub fn run_if(mut self, condition: RunCondition) -> Self {
+        self.conditions.push(condition);
+        self
+    }
+
+    /// Checks the active states and evaluates every run condition, without stopping at the first false one.
+    pub fn should_run(&mut self, ctx: &Context) -> bool {
+        self.conditions.iter_mut()
+            .fold(self.state_filter.evaluate(ctx), |should_run, condition| condition.evaluate(ctx) & should_run)
+    }
+
+    fn describe(&self) -> String {
+        match &self.label {
+            Some(label) => format!("'{}'", label),
+            None => format!("'{}'", self.name)
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScheduleError {
+    DuplicateLabel { stage: Stage, label: String },
+    MissingLabel { stage: Stage, system: String, label: String },
+    Cycle { stage: Stage, systems: Vec<String> }
+}
+
+impl fmt::Display for ScheduleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScheduleError::DuplicateLabel { stage, label } => {
+                write!(f, "label '{}' is used by several systems in stage {:?}", label, stage)
+            }
+            ScheduleError::MissingLabel { stage, system, label } => {
+                write!(f, "system {} in stage {:?} is ordered against unknown label '{}'", system, stage, label)
+            }
+            ScheduleError::Cycle { stage, systems } => {
+                write!(f, "ordering cycle in stage {:?} between systems {}", stage, systems.join(", "))
+            }
+        }
     }
 }
 
+impl std::error::Error for ScheduleError {}
+
 pub struct Schedule {
-    systems: BTreeMap<Stage, Vec<System>>
+    systems: BTreeMap<Stage, Vec<System>>,
+    state_drivers: Vec<StateDriver>
 }
 
 impl Schedule {
     pub fn new() -> Self {
         Self {
-            systems: BTreeMap::new()
+            systems: BTreeMap::new(),
+            state_drivers: Vec::new()
+        }
+    }
+
+    /// Registers the state machine of `S` so that its transitions run `OnEnter` / `OnExit`.
+    /// The `StateMachine<S>` resource itself is inserted by `App::add_state`.
+    pub fn add_state_type<S: States>(&mut self) {
+        self.state_drivers.push(StateDriver::of::<S>());
+    }
+
+    /// Runs `OnEnter` for the initial value of every registered state type.
+    pub fn enter_initial_states(&mut self, ctx: &mut Context) {
+        for driver in self.state_drivers.clone() {
+            (driver.enter_initial)(self, ctx);
         }
     }
 
@@ -55,13 +218,252 @@ impl Schedule {
         self.systems.entry(stage).or_default().push(system);
     }
 
-    pub fn run_stage(&self, stage: Stage, ctx: &mut Context) {
-        if let Some(systems) = self.systems.get(&stage) {
+    /// Sorts every stage so that `before` / `after` constraints are satisfied.
+    /// Systems without constraints keep their registration order.
+    pub fn build(&mut self) -> Result<(), ScheduleError> {
+        for (stage, systems) in self.systems.iter_mut() {
+            let order = Self::sort_stage(*stage, systems)?;
+
+            let mut slots: Vec<Option<System>> = systems.drain(..).map(Some).collect();
+            systems.extend(order.into_iter().filter_map(|index| slots[index].take()));
+        }
+
+        Ok(())
+    }
+
+    fn sort_stage(stage: Stage, systems: &[System]) -> Result<Vec<usize>, ScheduleError> {
+        let mut labels: HashMap<&str, usize> = HashMap::new();
+        for (index, system) in systems.iter().enumerate() {
+            if let Some(label) = &system.label
+                && labels.insert(label.as_str(), index).is_some()
+            {
+                return Err(ScheduleError::DuplicateLabel { stage, label: label.clone() });
+            }
+        }
+
+        let resolve = |index: usize, label: &String| -> Result<usize, ScheduleError> {
+            labels.get(label.as_str()).copied().ok_or_else(|| ScheduleError::MissingLabel {
+                stage,
+                system: systems[index].describe(),
+                label: label.clone()
+            })
+        };
+
+        // Edge `a -> b` means `a` runs before `b`
+        let mut successors: Vec<Vec<usize>> = vec![Vec::new(); systems.len()];
+        let mut in_degree = vec![0usize; systems.len()];
+
+        for (index, system) in systems.iter().enumerate() {
+            for label in &system.before {
+                let target = resolve(index, label)?;
+                successors[index].push(target);
+                in_degree[target] += 1;
+            }
+            for label in &system.after {
+                let source = resolve(index, label)?;
+                successors[source].push(index);
+                in_degree[index] += 1;
+            }
+        }
+
+        // Kahn's algorithm, always picking the earliest registered ready system
+        let mut ready: BTreeSet<usize> = (0..systems.len()).filter(|&i| in_degree[i] == 0).collect();
+        let mut order = Vec::with_capacity(systems.len());
+
+        while let Some(index) = ready.pop_first() {
+            order.push(index);
+            for &next in &successors[index] {
+                in_degree[next] -= 1;
+                if in_degree[next] == 0 {
+                    ready.insert(next);
+                }
+            }
+        }
+
+        if order.len() != systems.len() {
+            let systems = (0..systems.len())
+                .filter(|&i| in_degree[i] > 0)
+                .map(|i| systems[i].describe())
+                .collect();
+            return Err(ScheduleError::Cycle { stage, systems });
+        }
+
+        Ok(order)
+    }
+
+    /// Applies the transitions queued with `request_state`, `push_state` and `pop_state`
+    /// for every state type, running the matching `OnExit` / `OnEnter` stages.
+    /// Transitions queued by those stages are applied in the same pass.
+    pub fn apply_state_transitions(&mut self, ctx: &mut Context) {
+        const MAX_PASSES: usize = 16;
+
+        for _ in 0..MAX_PASSES {
+            let mut applied = false;
+            for driver in self.state_drivers.clone() {
+                applied |= (driver.apply_transitions)(self, ctx);
+            }
+
+            if !applied {
+                return;
+            }
+        }
+
+        warn!("Schedule: State transitions kept queuing new transitions, stopping after {} passes", MAX_PASSES);
+    }
+
+    pub fn run_stage(&mut self, stage: Stage, ctx: &mut Context) {
+        // Timings are only taken when a `Profiler` resource is present and enabled
+        let now = |ctx: &Context| ctx.get_resource::<Profiler>()
+            .filter(|profiler| profiler.enabled)
+            .map(|profiler| profiler.now());
+
+        let stage_start = now(ctx);
+        let mut system_profiles = Vec::new();
+
+        if let Some(systems) = self.systems.get_mut(&stage) {
             for system in systems {
-                if system.is_active(ctx.game_state) {
+                if system.should_run(ctx) {
+                    let start = now(ctx);
+
+                    ctx.last_run = system.last_run;
                     (system.func)(ctx);
+                    // Writes stamped during the run are not seen by the system itself
+                    if let Some(change_ticks) = ctx.get_resource_mut::<ChangeTicks>() {
+                        system.last_run = change_ticks.advance();
+                    }
+
+                    if let Some(start) = start && let Some(end) = now(ctx) {
+                        system_profiles.push(SystemProfile {
+                            name: system.name.clone(),
+                            start,
+                            duration: end - start
+                        });
+                    }
                 }
             }
         }
+
+        // Deferred spawns / despawns are visible to the next stage
+        Commands::apply(ctx);
+
+        if let Some(start) = stage_start && let Some(profiler) = ctx.get_resource_mut::<Profiler>() {
+            let duration = profiler.now() - start;
+            profiler.record_stage(StageProfile {
+                stage: format!("{:?}", stage),
+                start,
+                duration,
+                systems: system_profiles
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hecs::World;
+
+    use super::*;
+    use crate::core::state::{GameState, StateMachine};
+    use crate::prelude::AssetServer;
+
+    fn system(label: &str) -> System {
+        System::new(|_: &mut Context| {}, vec![GameState::Playing]).label(label)
+    }
+
+    fn sorted_labels(systems: Vec<System>) -> Result<Vec<String>, ScheduleError> {
+        let order = Schedule::sort_stage(Stage::Update, &systems)?;
+        Ok(order.into_iter().map(|index| systems[index].label.clone().unwrap()).collect())
+    }
+
+    #[test]
+    fn constraints_reorder_and_keep_registration_order_otherwise() {
+        let systems = vec![
+            system("render").after("physics"),
+            system("input"),
+            system("physics").after("input"),
+            system("audio")
+        ];
+
+        assert_eq!(sorted_labels(systems).unwrap(), ["input", "physics", "render", "audio"]);
+    }
+
+    #[test]
+    fn label_cycle_is_an_error() {
+        let systems = vec![
+            system("a").before("b"),
+            system("b").before("c"),
+            system("c").before("a"),
+            system("free")
+        ];
+
+        assert_eq!(sorted_labels(systems), Err(ScheduleError::Cycle {
+            stage: Stage::Update,
+            systems: vec!["'a'".to_string(), "'b'".to_string(), "'c'".to_string()]
+        }));
+    }
+
+    #[test]
+    fn missing_label_is_an_error() {
+        let systems = vec![
+            system("camera").after("hierarchy")
+        ];
+
+        assert_eq!(sorted_labels(systems), Err(ScheduleError::MissingLabel {
+            stage: Stage::Update,
+            system: "'camera'".to_string(),
+            label: "hierarchy".to_string()
+        }));
+    }
+
+    #[test]
+    fn duplicate_label_is_an_error() {
+        let systems = vec![system("physics"), system("physics")];
+
+        assert_eq!(sorted_labels(systems), Err(ScheduleError::DuplicateLabel {
+            stage: Stage::Update,
+            label: "physics".to_string()
+        }));
+    }
+
+    #[test]
+    fn profiler_names_state_stages_and_records_only_when_enabled() {
+        let mut ctx = Context::new(World::new(), AssetServer::new());
+        ctx.insert_resource(StateMachine::new(GameState::Playing));
+        ctx.insert_resource(Profiler::default());
+
+        let mut schedule = Schedule::new();
+        schedule.add_system(Stage::on_enter(GameState::Paused), system("pause_menu"));
+        schedule.build().unwrap();
+
+        schedule.run_stage(Stage::on_enter(GameState::Paused), &mut ctx);
+        ctx.resource_mut::<Profiler>().end_frame(0, 0);
+        assert!(ctx.resource::<Profiler>().last_frame().unwrap().stages.is_empty());
+
+        ctx.resource_mut::<Profiler>().enabled = true;
+        schedule.run_stage(Stage::on_enter(GameState::Paused), &mut ctx);
+        ctx.resource_mut::<Profiler>().end_frame(0, 0);
+
+        let stages: Vec<String> = ctx.resource::<Profiler>().last_frame().unwrap().stages.iter()
+            .map(|stage| stage.stage.clone())
+            .collect();
+        assert_eq!(stages, ["OnEnter(GameState::Paused)"]);
+    }
+
+    #[test]
+    fn always_systems_run_without_a_state_machine() {
+        #[derive(Default)]
+        struct Runs(u32);
+
+        let mut ctx = Context::new(World::new(), AssetServer::new());
+        ctx.insert_resource(Runs::default());
+
+        let mut schedule = Schedule::new();
+        schedule.add_system(Stage::Update, System::always(|ctx: &mut Context| ctx.resource_mut::<Runs>().0 += 1));
+        schedule.add_system(Stage::Update, System::new(|ctx: &mut Context| ctx.resource_mut::<Runs>().0 += 10, vec![GameState::Playing]));
+        schedule.build().unwrap();
+
+        schedule.run_stage(Stage::Update, &mut ctx);
+
+        assert_eq!(ctx.resource::<Runs>().0, 1);
     }
 }
@@ -0,0 +1,72 @@
+use macroquad::prelude::*;
+
+use crate::core::web_context::WebContext;
+
+/// Size, DPI scale and focus of the window, refreshed by the `App` at the start of every frame.
+/// Systems should read it instead of calling `screen_width()` / `screen_height()`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScreenInfo {
+    /// Logical size, in the same units as the macroquad drawing functions.
+    pub width: f32,
+    pub height: f32,
+    pub dpi_scale: f32,
+    /// Only tracked on the web with the `web-focus` feature, always true otherwise. See `WebContext::has_focus`.
+    pub focused: bool
+}
+
+impl ScreenInfo {
+    pub fn new(width: f32, height: f32) -> Self {
+        Self {
+            width,
+            height,
+            dpi_scale: 1.0,
+            focused: true
+        }
+    }
+
+    /// Reads the current window state from macroquad. Requires a window.
+    pub fn current() -> Self {
+        Self {
+            width: screen_width(),
+            height: screen_height(),
+            dpi_scale: screen_dpi_scale(),
+            focused: WebContext::has_focus()
+        }
+    }
+
+    pub fn size(&self) -> Vec2 {
+        vec2(self.width, self.height)
+    }
+
+    pub fn center(&self) -> Vec2 {
+        self.size() * 0.5
+    }
+
+    /// Physical size in pixels.
+    pub fn physical_size(&self) -> Vec2 {
+        self.size() * self.dpi_scale
+    }
+}
+
+/// Sent when the size of the window changes, with the new logical size.
+#[derive(Debug, Clone, Copy)]
+pub struct WindowResized {
+    pub width: f32,
+    pub height: f32
+}
+
+/// Sent when the DPI scale changes, e.g. when the window moves to another monitor.
+#[derive(Debug, Clone, Copy)]
+pub struct WindowScaleChanged {
+    pub dpi_scale: f32
+}
+
+/// Sent when the page loses the keyboard focus. Web builds with the `web-focus` feature only,
+/// never sent on desktop. See `WebContext::has_focus`.
+#[derive(Debug, Clone, Copy)]
+pub struct WindowFocusLost;
+
+/// Sent when the page gets the keyboard focus back. Web builds with the `web-focus` feature only,
+/// never sent on desktop. See `WebContext::has_focus`.
+#[derive(Debug, Clone, Copy)]
+pub struct WindowFocusGained;
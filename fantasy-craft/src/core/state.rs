@@ -0,0 +1,199 @@
+use std::any::{TypeId, type_name};
+use std::collections::BTreeSet;
+use std::collections::hash_map::DefaultHasher;
+use std::fmt::{self, Debug};
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+
+use macroquad::logging::warn;
+
+use crate::core::context::Context;
+use crate::core::schedule::{Schedule, Stage};
+
+/// A type usable as a state machine. Each implementing type is an independent
+/// state machine, registered with `App::add_state`.
+///
+/// ```ignore
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// enum DialogueState { Closed, Open }
+///
+/// impl States for DialogueState {}
+/// ```
+pub trait States: Copy + Eq + Hash + Debug + Send + Sync + 'static {}
+
+/// The default state type used by the engine plugins.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GameState {
+    Menu,
+    Playing,
+    Paused,
+    Loading
+}
+
+impl States for GameState {}
+
+/// Identifies one value of one state type. Keys the `OnEnter` / `OnExit` stages.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StateId {
+    type_id: TypeId,
+    value: u64,
+    name: &'static str
+}
+
+// Names of the state values seen so far, so `StateId` stays `Copy`
+static STATE_NAMES: Mutex<BTreeSet<&'static str>> = Mutex::new(BTreeSet::new());
+
+impl StateId {
+    pub fn of<S: States>(state: S) -> Self {
+        let mut hasher = DefaultHasher::new();
+        state.hash(&mut hasher);
+
+        let type_name = type_name::<S>();
+        let name = format!("{}::{:?}", type_name.rsplit("::").next().unwrap_or(type_name), state);
+
+        Self {
+            type_id: TypeId::of::<S>(),
+            value: hasher.finish(),
+            name: intern_state_name(name)
+        }
+    }
+
+    /// The state value as written in code, e.g. `GameState::Playing`.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl Debug for StateId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name)
+    }
+}
+
+fn intern_state_name(name: String) -> &'static str {
+    let mut names = STATE_NAMES.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    match names.get(name.as_str()) {
+        Some(interned) => interned,
+        None => {
+            let interned: &'static str = Box::leak(name.into_boxed_str());
+            names.insert(interned);
+            interned
+        }
+    }
+}
+
+/// A state change requested through the `Context`, applied at the next frame boundary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StateTransition<S: States> {
+    /// Replaces the current state: runs `OnExit(old)` then `OnEnter(new)`.
+    Set(S),
+    /// Stacks a state on top of the current one, which is kept as is: runs `OnEnter(new)`.
+    Push(S),
+    /// Leaves the top state and resumes the one below: runs `OnExit(top)`.
+    Pop
+}
+
+/// Resource holding the current value, the stack and the pending transitions of one state type.
+#[derive(Debug)]
+pub struct StateMachine<S: States> {
+    current: S,
+    stack: Vec<S>,
+    pending: Vec<StateTransition<S>>
+}
+
+impl<S: States> StateMachine<S> {
+    pub fn new(initial: S) -> Self {
+        Self {
+            current: initial,
+            stack: Vec::new(),
+            pending: Vec::new()
+        }
+    }
+
+    pub fn current(&self) -> S {
+        self.current
+    }
+
+    /// States below the current one, pushed with `push_state`.
+    pub fn stack(&self) -> &[S] {
+        &self.stack
+    }
+
+    pub fn queue(&mut self, transition: StateTransition<S>) {
+        self.pending.push(transition);
+    }
+
+    pub fn take_transitions(&mut self) -> Vec<StateTransition<S>> {
+        std::mem::take(&mut self.pending)
+    }
+}
+
+/// Type-erased hooks the `Schedule` keeps for every registered state type.
+#[derive(Clone, Copy)]
+pub(crate) struct StateDriver {
+    pub enter_initial: fn(&mut Schedule, &mut Context),
+    pub apply_transitions: fn(&mut Schedule, &mut Context) -> bool
+}
+
+impl StateDriver {
+    pub fn of<S: States>() -> Self {
+        Self {
+            enter_initial: enter_initial::<S>,
+            apply_transitions: apply_transitions::<S>
+        }
+    }
+}
+
+fn enter_initial<S: States>(schedule: &mut Schedule, ctx: &mut Context) {
+    if let Some(machine) = ctx.get_resource::<StateMachine<S>>() {
+        let initial = machine.current();
+        schedule.run_stage(Stage::on_enter(initial), ctx);
+    }
+}
+
+/// Applies the queued transitions of `S`. Returns false when there was nothing to apply.
+fn apply_transitions<S: States>(schedule: &mut Schedule, ctx: &mut Context) -> bool {
+    let transitions = match ctx.get_resource_mut::<StateMachine<S>>() {
+        Some(machine) => machine.take_transitions(),
+        None => return false
+    };
+
+    if transitions.is_empty() {
+        return false;
+    }
+
+    for transition in transitions {
+        let current = ctx.state::<S>();
+
+        match transition {
+            StateTransition::Set(state) => {
+                if state == current {
+                    continue;
+                }
+                schedule.run_stage(Stage::on_exit(current), ctx);
+                ctx.resource_mut::<StateMachine<S>>().current = state;
+                schedule.run_stage(Stage::on_enter(state), ctx);
+            }
+            StateTransition::Push(state) => {
+                let machine = ctx.resource_mut::<StateMachine<S>>();
+                machine.stack.push(current);
+                machine.current = state;
+                schedule.run_stage(Stage::on_enter(state), ctx);
+            }
+            StateTransition::Pop => {
+                if ctx.resource::<StateMachine<S>>().stack.is_empty() {
+                    warn!("StateMachine: pop_state::<{}> called with an empty state stack", std::any::type_name::<S>());
+                    continue;
+                }
+                schedule.run_stage(Stage::on_exit(current), ctx);
+                let machine = ctx.resource_mut::<StateMachine<S>>();
+                if let Some(previous) = machine.stack.pop() {
+                    machine.current = previous;
+                }
+            }
+        }
+    }
+
+    true
+}
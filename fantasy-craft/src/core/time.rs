@@ -1,2 +1,169 @@
+/// Delta of the stage currently running: the scaled frame time, or the fixed timestep during `FixedUpdate`.
 #[derive(Debug, Clone, Copy)]
 pub struct DeltaTime(pub f32);
+
+/// Frame clock of the game.
+///
+/// The scaled delta follows `time_scale` and drops to zero while `paused`, which also stops `FixedUpdate`.
+/// Gameplay reads it through `DeltaTime`; GUI and input systems should use the unscaled values.
+#[derive(Debug, Clone, Copy)]
+pub struct Time {
+    pub time_scale: f32,
+    pub paused: bool,
+    delta: f32,
+    unscaled_delta: f32,
+    elapsed: f64,
+    unscaled_elapsed: f64,
+    frame_count: u64
+}
+
+impl Time {
+    pub fn new() -> Self {
+        Self {
+            time_scale: 1.0,
+            paused: false,
+            delta: 0.0,
+            unscaled_delta: 0.0,
+            elapsed: 0.0,
+            unscaled_elapsed: 0.0,
+            frame_count: 0
+        }
+    }
+
+    /// Starts a new frame that lasted `frame_delta` real seconds.
+    pub fn advance(&mut self, frame_delta: f32) {
+        self.unscaled_delta = frame_delta;
+        self.delta = if self.paused { 0.0 } else { frame_delta * self.time_scale.max(0.0) };
+
+        self.unscaled_elapsed += self.unscaled_delta as f64;
+        self.elapsed += self.delta as f64;
+        self.frame_count += 1;
+    }
+
+    /// Scaled frame time in seconds, zero while paused.
+    pub fn delta(&self) -> f32 {
+        self.delta
+    }
+
+    /// Real frame time in seconds, ignores `time_scale` and `paused`.
+    pub fn unscaled_delta(&self) -> f32 {
+        self.unscaled_delta
+    }
+
+    /// Scaled time elapsed since the start of the game.
+    pub fn elapsed(&self) -> f64 {
+        self.elapsed
+    }
+
+    /// Real time elapsed since the start of the game.
+    pub fn unscaled_elapsed(&self) -> f64 {
+        self.unscaled_elapsed
+    }
+
+    /// Number of frames started since the start of the game.
+    pub fn frame_count(&self) -> u64 {
+        self.frame_count
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+    }
+}
+
+impl Default for Time {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Drives the `FixedUpdate` stage at a constant tick rate, independently of the frame rate.
+///
+/// Each frame the elapsed time is added to an accumulator which is then consumed in
+/// `timestep` slices. `alpha` is the fraction of a tick left in the accumulator and can be
+/// used by render systems to interpolate between the previous and the current tick.
+#[derive(Debug, Clone, Copy)]
+pub struct FixedTime {
+    pub timestep: f32,
+    pub max_substeps: u32,
+    accumulator: f32,
+    alpha: f32
+}
+
+impl FixedTime {
+    pub fn new(tick_rate: f32, max_substeps: u32) -> Self {
+        Self {
+            timestep: 1.0 / tick_rate,
+            max_substeps,
+            accumulator: 0.0,
+            alpha: 0.0
+        }
+    }
+
+    /// Interpolation factor (0.0 - 1.0) between the last two fixed ticks.
+    pub fn alpha(&self) -> f32 {
+        self.alpha
+    }
+
+    /// Adds the frame time to the accumulator and returns how many ticks must run.
+    /// Time that does not fit in `max_substeps` ticks is dropped to avoid a spiral of death.
+    pub fn accumulate(&mut self, frame_delta: f32) -> u32 {
+        self.accumulator += frame_delta;
+
+        let mut steps = 0;
+        while self.accumulator >= self.timestep && steps < self.max_substeps {
+            self.accumulator -= self.timestep;
+            steps += 1;
+        }
+
+        if steps == self.max_substeps {
+            self.accumulator = self.accumulator.min(self.timestep);
+        }
+
+        self.alpha = (self.accumulator / self.timestep).clamp(0.0, 1.0);
+        steps
+    }
+}
+
+impl Default for FixedTime {
+    fn default() -> Self {
+        Self::new(60.0, 5)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accumulate_runs_whole_ticks_and_keeps_the_remainder() {
+        let mut fixed_time = FixedTime::new(10.0, 5);
+
+        assert_eq!(fixed_time.accumulate(0.25), 2);
+        assert!((fixed_time.alpha() - 0.5).abs() < 1e-4);
+
+        // The remaining 0.05s completes a tick with the next frame
+        assert_eq!(fixed_time.accumulate(0.06), 1);
+        assert!((fixed_time.alpha() - 0.1).abs() < 1e-4);
+
+        assert_eq!(fixed_time.accumulate(0.0), 0);
+    }
+
+    #[test]
+    fn accumulate_drops_the_time_beyond_max_substeps() {
+        let mut fixed_time = FixedTime::new(10.0, 3);
+
+        // A 2s hitch only runs 3 ticks, and at most one tick is carried over
+        assert_eq!(fixed_time.accumulate(2.0), 3);
+        assert!(fixed_time.alpha() <= 1.0);
+        assert_eq!(fixed_time.accumulate(0.0), 1);
+        assert_eq!(fixed_time.accumulate(0.0), 0);
+    }
+}
@@ -5,6 +5,12 @@ unsafe extern "C" {
     fn js_get_base_url(ptr: *mut u8, cap: i32) -> i32;
 }
 
+// Only imported with the `web-focus` feature, so pages loading the stock `mq_js_bundle.js` alone still start
+#[cfg(all(target_arch = "wasm32", feature = "web-focus"))]
+unsafe extern "C" {
+    fn js_has_focus() -> i32;
+}
+
 pub struct WebContext;
 
 impl WebContext {
@@ -29,4 +35,20 @@ impl WebContext {
             String::from("")
         }
     }
+    /// Whether the page has the keyboard focus (`document.hasFocus()`).
+    ///
+    /// Web only, with the `web-focus` feature: the value comes from the `js/fantasy_craft.js` miniquad
+    /// plugin, which the page must then load next to `mq_js_bundle.js`. Without the feature, and on
+    /// desktop where macroquad does not forward the focus changes of the window, this is always true.
+    pub fn has_focus() -> bool {
+        #[cfg(all(target_arch = "wasm32", feature = "web-focus"))]
+        unsafe {
+            js_has_focus() != 0
+        }
+
+        #[cfg(not(all(target_arch = "wasm32", feature = "web-focus")))]
+        {
+            true
+        }
+    }
 }
@@ -2,10 +2,14 @@ use std::sync::Arc;
 use macroquad::math::Vec2;
 
 use crate::core::plugins::Plugin;
-use crate::prelude::{GameState, Stage, System};
+use crate::prelude::{GameState, Stage, System, has_window};
 use crate::scene::scene_loader::ComponentLoader;
 use crate::{graphics::sprites::Spritesheet, prelude::Context};
+use crate::core::time::FixedTime;
 use crate::physics::components::Transform;
+use crate::physics::helpers::interpolated_position;
+use crate::core::error::EngineError;
+use crate::scene::scene_loader::{insert_component, parse_component, save_component};
 
 pub struct AnimationKeyFrame {
     pub column: u32,
@@ -59,13 +63,16 @@ pub struct AnimationComponent(pub String);
 pub struct AnimationComponentLoader;
 
 impl ComponentLoader for AnimationComponentLoader {
-    fn load(&self, ctx: &mut Context, entity: hecs::Entity, data: &serde_json::Value) {
-        let animation_name: String = serde_json::from_value(data.clone())
-            .unwrap_or_default();
+    fn load(&self, ctx: &mut Context, entity: hecs::Entity, data: &serde_json::Value) -> Result<(), EngineError> {
+        let animation_name: String = parse_component("AnimationComponent", data)?;
 
         let component = AnimationComponent(animation_name);
 
-        ctx.world.insert_one(entity, component).expect("Failed to insert AnimationComponent");
+        insert_component(ctx, entity, component)
+    }
+
+    fn save(&self, ctx: &Context, entity: hecs::Entity) -> Option<serde_json::Value> {
+        save_component(ctx, entity, |component: &AnimationComponent| component.0.clone())
     }
 }
 
@@ -80,9 +87,21 @@ pub fn update_animations(ctx: &mut Context) {
 }
 
 pub fn animation_render_system(ctx: &mut Context) {
-    for (_, (animation_comp, transform)) in ctx.world.query::<(&AnimationComponent, &Transform)>().iter() {
-        if let Some(animation) = ctx.asset_server.get_animation_mut(&animation_comp.0) {
-            animation.draw(transform.position.x, transform.position.y, transform.scale);
+    let alpha = ctx.get_resource::<FixedTime>().map_or(1.0, |fixed_time| fixed_time.alpha());
+
+    // Smooth bodies moved by the fixed timestep, and their children, between the last two ticks
+    let draws: Vec<(String, Vec2, Vec2)> = ctx.world.query::<(&AnimationComponent, &Transform)>()
+        .iter()
+        .map(|(entity, (animation_comp, transform))| (
+            animation_comp.0.clone(),
+            interpolated_position(ctx, entity, alpha).unwrap_or(transform.position),
+            transform.scale
+        ))
+        .collect();
+
+    for (animation_name, position, scale) in draws {
+        if let Some(animation) = ctx.asset_server.get_animation_mut(&animation_name) {
+            animation.draw(position.x, position.y, scale);
         }
     }
 }
@@ -102,6 +121,6 @@ impl Plugin for AnimationPlugin {
             .add_system(Stage::Render, System::new(
                 animation_render_system,
                 vec![GameState::Playing, GameState::Menu]
-            ));
+            ).run_if(has_window()));
     }
 }
@@ -1,5 +1,7 @@
 use macroquad::prelude::*;
 use crate::gui::gui_element::GuiElement;
+use crate::core::event::EventBus;
+use crate::core::screen::WindowResized;
 use crate::prelude::{Context, Transform};
 use crate::gui::gui_box::GuiBox;
 use crate::gui::gui_dimension::GuiDimension;
@@ -21,14 +23,15 @@ pub struct SplashAnimation {
     pub total_duration: f32
 }
 
+fn centered_logo_position(screen_size: Vec2, logo_size: Vec2) -> Vec2 {
+    (screen_size - logo_size) / 2.0
+}
+
 pub fn setup_splash_screen(ctx: &mut Context) {
-    let screen_w = screen_width();
-    let screen_h = screen_height();
     let logo_w = 400.0;
     let logo_h = 400.0;
 
-    let pos_x = (screen_w / 2.0) - (logo_w / 2.0);
-    let pos_y = (screen_h / 2.0) - (logo_h / 2.0);
+    let position = centered_logo_position(ctx.screen().size(), vec2(logo_w, logo_h));
 
     let splash_screen_data = ctx.get_resource::<SplashScreenData>();
 
@@ -39,9 +42,9 @@ pub fn setup_splash_screen(ctx: &mut Context) {
         BLACK
     };
     
-    ctx.world.spawn((
+    ctx.spawn((
         Transform {
-            position: vec2(pos_x, pos_y),
+            position,
             ..Default::default()
         },
         GuiBox {
@@ -68,8 +71,10 @@ pub fn setup_splash_screen(ctx: &mut Context) {
 }
 
 pub fn animate_splash_screen(ctx: &mut Context) {
-    for (_, (transform, gui_image, anim)) in ctx.world.query::<(&mut Transform, &mut GuiImage, &mut SplashAnimation)>().iter() {
-        anim.timer += ctx.dt();
+    let mut scaled = Vec::new();
+
+    for (entity, (transform, gui_image, anim)) in ctx.world.query::<(&mut Transform, &mut GuiImage, &mut SplashAnimation)>().iter() {
+        anim.timer += ctx.unscaled_dt();
 
         if anim.timer < anim.fade_in_time {
             let t = anim.timer / anim.fade_in_time;
@@ -79,12 +84,41 @@ pub fn animate_splash_screen(ctx: &mut Context) {
             let t = (anim.timer - anim.fade_in_time) * 2.0;
             transform.scale = vec2(1.0 + 0.02 * (t.sin()), 1.0 + 0.02 * (t.sin()));
             gui_image.tint.a = 1.0;
+            scaled.push(entity);
         }
         else {
             let t = (anim.total_duration - anim.timer) / anim.fade_out_time;
             gui_image.tint.a = t.clamp(0.0, 1.0);
         }
     }
+
+    for entity in scaled {
+        ctx.mark_changed::<Transform>(entity);
+    }
+}
+
+/// Keeps the logo centered when the window is resized during the splash screen.
+pub fn recenter_splash_screen(ctx: &mut Context) {
+    let resized = ctx.get_resource::<EventBus>()
+        .is_some_and(|event_bus| event_bus.read::<WindowResized>().next().is_some());
+
+    if !resized {
+        return;
+    }
+
+    let screen_size = ctx.screen().size();
+    let mut moved = Vec::new();
+
+    for (entity, (transform, gui_box, _)) in ctx.world.query::<(&mut Transform, &GuiBox, &SplashScreenTag)>().iter() {
+        if let (GuiDimension::Pixels(logo_w), GuiDimension::Pixels(logo_h)) = (gui_box.width, gui_box.height) {
+            transform.position = centered_logo_position(screen_size, vec2(logo_w, logo_h));
+            moved.push(entity);
+        }
+    }
+
+    for entity in moved {
+        ctx.mark_changed::<Transform>(entity);
+    }
 }
 
 pub fn despawn_splash_screen(ctx: &mut Context) {
@@ -95,6 +129,6 @@ pub fn despawn_splash_screen(ctx: &mut Context) {
     }
 
     for entity in entities_to_despawn {
-        ctx.world.despawn(entity).expect("Failed to despawn splash entity");
+        ctx.despawn(entity).expect("Failed to despawn splash entity");
     }
 }
\ No newline at end of file
@@ -1,8 +1,10 @@
 use hecs::Entity;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 use crate::scene::scene_loader::ComponentLoader;
+use crate::core::error::EngineError;
+use crate::scene::scene_loader::{insert_component, parse_component, save_component, save_marker};
 
 #[derive(Debug)]
 pub struct TileMapComponent(pub String);
@@ -10,13 +12,16 @@ pub struct TileMapComponent(pub String);
 pub struct TileMapComponentLoader;
 
 impl ComponentLoader for TileMapComponentLoader {
-    fn load(&self, ctx: &mut crate::prelude::Context, entity: Entity, data: &Value) {
-        let map_name: String = serde_json::from_value(data.clone())
-            .unwrap_or_default();
+    fn load(&self, ctx: &mut crate::prelude::Context, entity: Entity, data: &Value) -> Result<(), EngineError> {
+        let map_name: String = parse_component("TileMapComponent", data)?;
 
         let component = TileMapComponent(map_name);
 
-        ctx.world.insert_one(entity, component).expect("Failed to insert TileMapComponent");
+        insert_component(ctx, entity, component)
+    }
+
+    fn save(&self, ctx: &crate::prelude::Context, entity: Entity) -> Option<Value> {
+        save_component(ctx, entity, |component: &TileMapComponent| component.0.clone())
     }
 }
 
@@ -26,7 +31,7 @@ pub struct TileMapLayerComponent {
     pub layer_name: String
 }
 
-#[derive(Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 struct TileMapLayerComponentLoaderData {
     #[serde(default)]
     pub tilemap_name: String,
@@ -38,16 +43,22 @@ struct TileMapLayerComponentLoaderData {
 pub struct TileMapLayerComponentLoader;
 
 impl ComponentLoader for TileMapLayerComponentLoader {
-    fn load(&self, ctx: &mut crate::prelude::Context, entity: Entity, data: &Value) {
-        let loader_data: TileMapLayerComponentLoaderData = serde_json::from_value(data.clone())
-            .unwrap_or_default();
+    fn load(&self, ctx: &mut crate::prelude::Context, entity: Entity, data: &Value) -> Result<(), EngineError> {
+        let loader_data: TileMapLayerComponentLoaderData = parse_component("TileMapLayerComponent", data)?;
 
         let component = TileMapLayerComponent {
             tilemap_name: loader_data.tilemap_name,
             layer_name: loader_data.layer_name
         };
 
-        ctx.world.insert_one(entity, component).expect("Failed to insert TileMapLayerComponent");
+        insert_component(ctx, entity, component)
+    }
+
+    fn save(&self, ctx: &crate::prelude::Context, entity: Entity) -> Option<Value> {
+        save_component(ctx, entity, |component: &TileMapLayerComponent| TileMapLayerComponentLoaderData {
+            tilemap_name: component.tilemap_name.clone(),
+            layer_name: component.layer_name.clone()
+        })
     }
 }
 
@@ -57,7 +68,11 @@ pub struct MainTileMap;
 pub struct MainTileMapLoader;
 
 impl ComponentLoader for MainTileMapLoader {
-    fn load(&self, ctx: &mut crate::prelude::Context, entity: Entity, _data: &Value) {
-        ctx.world.insert_one(entity, MainTileMap).expect("Failed to insert MainTileMap");
+    fn load(&self, ctx: &mut crate::prelude::Context, entity: Entity, _data: &Value) -> Result<(), EngineError> {
+        insert_component(ctx, entity, MainTileMap)
+    }
+
+    fn save(&self, ctx: &crate::prelude::Context, entity: Entity) -> Option<Value> {
+        save_marker::<MainTileMap>(ctx, entity)
     }
 }
\ No newline at end of file
@@ -1,4 +1,4 @@
-use crate::prelude::{GameState, MainTileMapLoader, Plugin, Stage, System, TileMapComponentLoader, TileMapLayerComponentLoader, tilemap_layer_render_system};
+use crate::prelude::{GameState, MainTileMapLoader, Plugin, Stage, System, TileMapComponentLoader, TileMapLayerComponentLoader, has_window, tilemap_layer_render_system};
 use crate::graphics::tiled_map::systems::tilemap_render_system;
 
 pub struct TiledMapPlugin;
@@ -14,10 +14,10 @@ impl Plugin for TiledMapPlugin {
             .add_system(Stage::Render, System::new(
                 tilemap_layer_render_system,
                 vec![GameState::Playing, GameState::Menu]
-            ))
+            ).run_if(has_window()))
             .add_system(Stage::Render, System::new(
                 tilemap_render_system,
                 vec![GameState::Playing, GameState::Menu]
-            ));
+            ).run_if(has_window()));
     }
 }
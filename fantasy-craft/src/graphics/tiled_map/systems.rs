@@ -17,10 +17,12 @@ fn get_visible_world_rect(ctx: &mut Context) -> Rect {
         break; 
     }
 
+    let screen = ctx.screen();
+
     // Le reste fonctionne exactement pareil, car on peut appeler des méthodes sur une référence
     if let Some(cam) = active_camera {
         let top_left = cam.screen_to_world(vec2(0.0, 0.0));
-        let bottom_right = cam.screen_to_world(vec2(screen_width(), screen_height()));
+        let bottom_right = cam.screen_to_world(screen.size());
         
         Rect::new(
             top_left.x, 
@@ -29,7 +31,7 @@ fn get_visible_world_rect(ctx: &mut Context) -> Rect {
             bottom_right.y - top_left.y
         )
     } else {
-        Rect::new(0.0, 0.0, screen_width(), screen_height())
+        Rect::new(0.0, 0.0, screen.width, screen.height)
     }
 }
 
@@ -1,6 +1,8 @@
 use hecs::Entity;
 
 use crate::prelude::ComponentLoader;
+use crate::core::error::EngineError;
+use crate::scene::scene_loader::{insert_component, parse_component, save_component};
 
 #[derive(Debug, Clone, Copy)]
 pub enum HorizontalAlignmentType {
@@ -34,13 +36,16 @@ pub struct HorizontalAlignment(pub HorizontalAlignmentType);
 pub struct HorizontalAlignmentLoader;
 
 impl ComponentLoader for HorizontalAlignmentLoader {
-    fn load(&self, ctx: &mut crate::prelude::Context, entity: Entity, data: &serde_json::Value) {
-        let loader_data: String = serde_json::from_value(data.clone())
-            .unwrap_or_default();
+    fn load(&self, ctx: &mut crate::prelude::Context, entity: Entity, data: &serde_json::Value) -> Result<(), EngineError> {
+        let loader_data: String = parse_component("HorizontalAlignment", data)?;
 
         let component = HorizontalAlignment(HorizontalAlignmentType::from_str(&loader_data));
 
-        ctx.world.insert_one(entity, component).expect("Failed to insert HorizontalAlignment");
+        insert_component(ctx, entity, component)
+    }
+
+    fn save(&self, ctx: &crate::prelude::Context, entity: Entity) -> Option<serde_json::Value> {
+        save_component(ctx, entity, |component: &HorizontalAlignment| component.0.to_str())
     }
 }
 
@@ -76,12 +81,15 @@ pub struct VerticalAlignment(pub VerticalAlignmentType);
 pub struct VerticalAlignmentLoader;
 
 impl ComponentLoader for VerticalAlignmentLoader {
-    fn load(&self, ctx: &mut crate::prelude::Context, entity: Entity, data: &serde_json::Value) {
-        let loader_data: String = serde_json::from_value(data.clone())
-            .unwrap_or_default();
+    fn load(&self, ctx: &mut crate::prelude::Context, entity: Entity, data: &serde_json::Value) -> Result<(), EngineError> {
+        let loader_data: String = parse_component("VerticalAlignment", data)?;
 
         let component = VerticalAlignment(VerticalAlignmentType::from_str(&loader_data));
 
-        ctx.world.insert_one(entity, component).expect("Failed to insert VerticalAlignment");
+        insert_component(ctx, entity, component)
+    }
+
+    fn save(&self, ctx: &crate::prelude::Context, entity: Entity) -> Option<serde_json::Value> {
+        save_component(ctx, entity, |component: &VerticalAlignment| component.0.to_str())
     }
 }
@@ -1,10 +1,21 @@
 use macroquad::prelude::*;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct ColorData {
     pub r: f32,
     pub g: f32,
     pub b: f32,
     pub a: f32
 }
+
+impl From<Color> for ColorData {
+    fn from(color: Color) -> Self {
+        Self {
+            r: color.r,
+            g: color.g,
+            b: color.b,
+            a: color.a
+        }
+    }
+}
@@ -1,4 +1,6 @@
 use crate::prelude::ComponentLoader;
+use crate::core::error::EngineError;
+use crate::scene::scene_loader::{insert_component, parse_component, save_component};
 
 #[derive(Debug)]
 pub struct FontComponent(pub String);
@@ -6,12 +8,15 @@ pub struct FontComponent(pub String);
 pub struct FontComponentLoader;
 
 impl ComponentLoader for FontComponentLoader {
-    fn load(&self, ctx: &mut crate::prelude::Context, entity: hecs::Entity, data: &serde_json::Value) {
-        let loader_data: String = serde_json::from_value(data.clone())
-            .unwrap_or_default();
+    fn load(&self, ctx: &mut crate::prelude::Context, entity: hecs::Entity, data: &serde_json::Value) -> Result<(), EngineError> {
+        let loader_data: String = parse_component("FontComponent", data)?;
 
         let component = FontComponent(loader_data);
 
-        ctx.world.insert_one(entity, component).expect("Failed to insert FontComponent");
+        insert_component(ctx, entity, component)
+    }
+
+    fn save(&self, ctx: &crate::prelude::Context, entity: hecs::Entity) -> Option<serde_json::Value> {
+        save_component(ctx, entity, |component: &FontComponent| component.0.clone())
     }
 }
@@ -1,14 +1,16 @@
 use hecs::Entity;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::prelude::ComponentLoader;
+use crate::core::error::EngineError;
+use crate::scene::scene_loader::{insert_component, parse_component, save_component};
 
 #[derive(Debug, Clone)]
 pub struct GuiAction {
     pub action_id: String
 }
 
-#[derive(Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct GuiActionLoaderData {
     pub action_id: String
 }
@@ -16,14 +18,19 @@ pub struct GuiActionLoaderData {
 pub struct GuiActionLoader;
 
 impl ComponentLoader for GuiActionLoader {
-    fn load(&self, ctx: &mut crate::prelude::Context, entity: Entity, data: &serde_json::Value) {
-        let loader_data: GuiActionLoaderData = serde_json::from_value(data.clone())
-            .unwrap_or_default();
+    fn load(&self, ctx: &mut crate::prelude::Context, entity: Entity, data: &serde_json::Value) -> Result<(), EngineError> {
+        let loader_data: GuiActionLoaderData = parse_component("GuiAction", data)?;
 
         let component = GuiAction {
             action_id: loader_data.action_id
         };
 
-        ctx.world.insert_one(entity, component).expect("Failed to insert GuiAction");
+        insert_component(ctx, entity, component)
+    }
+
+    fn save(&self, ctx: &crate::prelude::Context, entity: Entity) -> Option<serde_json::Value> {
+        save_component(ctx, entity, |component: &GuiAction| GuiActionLoaderData {
+            action_id: component.action_id.clone()
+        })
     }
 }
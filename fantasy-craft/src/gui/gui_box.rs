@@ -1,8 +1,10 @@
 use macroquad::prelude::*;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use crate::{gui::{alignment::{HorizontalAlignment, HorizontalAlignmentType, VerticalAlignment, VerticalAlignmentType}, gui_button::{ButtonState, GuiButton}, gui_dimension::{GuiDimension, GuiDimensionLoaderData}, resources::UiResolvedRects}, prelude::{ColorData, ComponentLoader, Context, Visible}};
+use crate::core::error::EngineError;
+use crate::scene::scene_loader::{insert_component, parse_component, save_component};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct GuiBox {
     pub width: GuiDimension,
     pub height: GuiDimension,
@@ -23,7 +25,7 @@ impl Default for GuiBox {
     }
 }
 
-#[derive(Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct GuiBoxLoaderData {
     #[serde(default)]
     pub width: GuiDimensionLoaderData,
@@ -39,9 +41,8 @@ pub struct GuiBoxLoaderData {
 pub struct GuiBoxLoader;
 
 impl ComponentLoader for GuiBoxLoader {
-    fn load(&self, ctx: &mut crate::prelude::Context, entity: hecs::Entity, data: &serde_json::Value) {
-        let loader_data: GuiBoxLoaderData = serde_json::from_value(data.clone())
-            .unwrap_or_default();
+    fn load(&self, ctx: &mut crate::prelude::Context, entity: hecs::Entity, data: &serde_json::Value) -> Result<(), EngineError> {
+        let loader_data: GuiBoxLoaderData = parse_component("GuiBox", data)?;
 
         let parse_dimension = |loader_dim: GuiDimensionLoaderData| -> GuiDimension {
             match loader_dim {
@@ -71,7 +72,25 @@ impl ComponentLoader for GuiBoxLoader {
             border_radius: loader_data.border_radius
         };
 
-        ctx.world.insert_one(entity, component).expect("Failed to insert GuiBox");
+        insert_component(ctx, entity, component)
+    }
+
+    fn save(&self, ctx: &crate::prelude::Context, entity: hecs::Entity) -> Option<serde_json::Value> {
+        save_component(ctx, entity, |component: &GuiBox| GuiBoxLoaderData {
+            width: component.width.into(),
+            height: component.height.into(),
+            color: component.color.into(),
+            screen_space: component.screen_space,
+            border_radius: component.border_radius
+        })
+    }
+}
+
+/// `on_remove` hook: forgets the resolved rectangle of a removed box right away,
+/// instead of waiting for the next layout pass.
+pub fn gui_box_removed_hook(ctx: &mut Context, entity: hecs::Entity) {
+    if let Some(rects) = ctx.get_resource_mut::<UiResolvedRects>() {
+        rects.0.remove(&entity);
     }
 }
 
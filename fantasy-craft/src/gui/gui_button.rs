@@ -1,7 +1,9 @@
 use macroquad::prelude::*;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{core::event::EventBus, gui::{alignment::{HorizontalAlignment, HorizontalAlignmentType, VerticalAlignment, VerticalAlignmentType}, event::UiClickEvent, gui_action::GuiAction, gui_box::GuiBox, resources::UiResolvedRects}, prelude::{ColorData, ComponentLoader, Context, Visible}};
+use crate::core::error::EngineError;
+use crate::scene::scene_loader::{insert_component, parse_component, save_component};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ButtonState {
@@ -50,7 +52,7 @@ impl Default for GuiButton {
     }
 }
 
-#[derive(Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct GuiButtonLoaderData {
     #[serde(default)]
     pub state: String,
@@ -65,9 +67,8 @@ pub struct GuiButtonLoaderData {
 pub struct GuiButtonLoader;
 
 impl ComponentLoader for GuiButtonLoader {
-    fn load(&self, ctx: &mut crate::prelude::Context, entity: hecs::Entity, data: &serde_json::Value) {
-        let loader_data: GuiButtonLoaderData = serde_json::from_value(data.clone())
-            .unwrap_or_default();
+    fn load(&self, ctx: &mut crate::prelude::Context, entity: hecs::Entity, data: &serde_json::Value) -> Result<(), EngineError> {
+        let loader_data: GuiButtonLoaderData = parse_component("GuiButton", data)?;
 
         let component = GuiButton {
             state: ButtonState::from_str(loader_data.state.as_str()),
@@ -92,7 +93,17 @@ impl ComponentLoader for GuiButtonLoader {
             )
         };
 
-        ctx.world.insert_one(entity, component).expect("Failed to insert GuiButton");
+        insert_component(ctx, entity, component)
+    }
+
+    fn save(&self, ctx: &crate::prelude::Context, entity: hecs::Entity) -> Option<serde_json::Value> {
+        save_component(ctx, entity, |component: &GuiButton| GuiButtonLoaderData {
+            state: component.state.to_str().to_string(),
+            just_clicked: component.just_clicked,
+            hovered_color: component.hovered_color.into(),
+            pressed_color: component.pressed_color.into(),
+            normal_color: component.normal_color.into()
+        })
     }
 }
 
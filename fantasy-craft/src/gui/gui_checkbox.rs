@@ -1,13 +1,15 @@
 use macroquad::prelude::*;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use crate::{gui::{gui_box::GuiBox, gui_button::GuiButton, resources::UiResolvedRects}, prelude::{ComponentLoader, Context, Visible}};
+use crate::core::error::EngineError;
+use crate::scene::scene_loader::{insert_component, parse_component, save_component};
 
 #[derive(Debug, Clone, Copy, Default)]
 pub struct GuiCheckbox {
     pub is_checked: bool
 }
 
-#[derive(Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct GuiCheckboxLoaderData {
     pub is_checked: bool
 }
@@ -15,15 +17,20 @@ pub struct GuiCheckboxLoaderData {
 pub struct GuiCheckboxLoader;
 
 impl ComponentLoader for GuiCheckboxLoader {
-    fn load(&self, ctx: &mut crate::prelude::Context, entity: hecs::Entity, data: &serde_json::Value) {
-        let loader_data: GuiCheckboxLoaderData = serde_json::from_value(data.clone())
-            .unwrap_or_default();
+    fn load(&self, ctx: &mut crate::prelude::Context, entity: hecs::Entity, data: &serde_json::Value) -> Result<(), EngineError> {
+        let loader_data: GuiCheckboxLoaderData = parse_component("GuiCheckbox", data)?;
 
         let component = GuiCheckbox {
             is_checked: loader_data.is_checked
         };
 
-        ctx.world.insert_one(entity, component).expect("Failed to insert GuiCheckbox");
+        insert_component(ctx, entity, component)
+    }
+
+    fn save(&self, ctx: &crate::prelude::Context, entity: hecs::Entity) -> Option<serde_json::Value> {
+        save_component(ctx, entity, |component: &GuiCheckbox| GuiCheckboxLoaderData {
+            is_checked: component.is_checked
+        })
     }
 }
 
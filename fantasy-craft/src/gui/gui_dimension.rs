@@ -1,6 +1,6 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum GuiDimension {
     Pixels(f32),
     Percent(f32)
@@ -21,13 +21,29 @@ impl Default for GuiDimension {
     }
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(untagged)]
 pub enum GuiDimensionLoaderData {
     Pixels(f32),
     Percent(String),
 }
 
+impl From<GuiDimension> for GuiDimensionLoaderData {
+    /// Percentages are written back as `"50%"`, the form the loaders read.
+    fn from(dimension: GuiDimension) -> Self {
+        match dimension {
+            GuiDimension::Pixels(px) => GuiDimensionLoaderData::Pixels(px),
+            GuiDimension::Percent(pct) => {
+                // Prefer the short form ("60%" rather than "60.000004%") when it reads back as the same value
+                let rounded = (pct * 100.0 * 10000.0).round() / 10000.0;
+                let value = if rounded / 100.0 == pct { rounded } else { pct * 100.0 };
+
+                GuiDimensionLoaderData::Percent(format!("{}%", value))
+            }
+        }
+    }
+}
+
 impl Default for GuiDimensionLoaderData {
     fn default() -> Self {
         GuiDimensionLoaderData::Pixels(100.0)
@@ -1,7 +1,9 @@
 use macroquad::prelude::*;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{gui::{gui_box::GuiBox, resources::{PreviousMousePosition, UiResolvedRects}}, prelude::{ComponentLoader, Context, Transform, Visible}};
+use crate::core::error::EngineError;
+use crate::scene::scene_loader::{insert_component, parse_component, save_component};
 
 #[derive(Debug, Clone, Copy)]
 pub struct GuiDraggable {
@@ -16,7 +18,7 @@ impl Default for GuiDraggable {
     }
 }
 
-#[derive(Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct GuiDraggableLoaderData {
     pub is_dragging: bool
 }
@@ -24,15 +26,20 @@ pub struct GuiDraggableLoaderData {
 pub struct GuiDraggableLoader;
 
 impl ComponentLoader for GuiDraggableLoader {
-    fn load(&self, ctx: &mut crate::prelude::Context, entity: hecs::Entity, data: &serde_json::Value) {
-        let loader_data: GuiDraggableLoaderData = serde_json::from_value(data.clone())
-            .unwrap_or_default();
+    fn load(&self, ctx: &mut crate::prelude::Context, entity: hecs::Entity, data: &serde_json::Value) -> Result<(), EngineError> {
+        let loader_data: GuiDraggableLoaderData = parse_component("GuiDraggable", data)?;
 
         let component = GuiDraggable {
             is_dragging: loader_data.is_dragging
         };
 
-        ctx.world.insert_one(entity, component).expect("Failed to insert GuiDraggable");
+        insert_component(ctx, entity, component)
+    }
+
+    fn save(&self, ctx: &crate::prelude::Context, entity: hecs::Entity) -> Option<serde_json::Value> {
+        save_component(ctx, entity, |component: &GuiDraggable| GuiDraggableLoaderData {
+            is_dragging: component.is_dragging
+        })
     }
 }
 
@@ -49,6 +56,7 @@ pub fn draggable_system(ctx: &mut Context) {
     // --- MODIFIED: Get map once ---
     let resolved_rects_map = &ctx.resource::<UiResolvedRects>().0;
 
+    let mut dragged = Vec::new();
     let mut query = ctx.world.query::<(&mut GuiDraggable, &mut Transform, &GuiBox, Option<&Visible>)>();
 
     for (entity, (draggable, transform, _gui_box, visibility)) in query.iter() {
@@ -74,6 +82,7 @@ pub fn draggable_system(ctx: &mut Context) {
                 // which will be used as the base pos next frame.
                 transform.position.x += delta.x;
                 transform.position.y += delta.y;
+                dragged.push(entity);
             }
         } else {
             // Use the transform's position for hover checking, as it's
@@ -90,4 +99,9 @@ pub fn draggable_system(ctx: &mut Context) {
             }
         }
     }
+    drop(query);
+
+    for entity in dragged {
+        ctx.mark_changed::<Transform>(entity);
+    }
 }
@@ -1,14 +1,20 @@
 use hecs::Entity;
 
 use crate::prelude::ComponentLoader;
+use crate::core::error::EngineError;
+use crate::scene::scene_loader::{insert_component, save_marker};
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
 pub struct GuiElement;
 
 pub struct GuiElementLoader;
 
 impl ComponentLoader for GuiElementLoader {
-    fn load(&self, ctx: &mut crate::prelude::Context, entity: Entity, _data: &serde_json::Value) {
-        ctx.world.insert_one(entity, GuiElement).expect("Failed to insert GuiElement");
+    fn load(&self, ctx: &mut crate::prelude::Context, entity: Entity, _data: &serde_json::Value) -> Result<(), EngineError> {
+        insert_component(ctx, entity, GuiElement)
+    }
+
+    fn save(&self, ctx: &crate::prelude::Context, entity: Entity) -> Option<serde_json::Value> {
+        save_marker::<GuiElement>(ctx, entity)
     }
 }
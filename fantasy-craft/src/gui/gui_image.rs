@@ -1,8 +1,10 @@
 use macroquad::prelude::*;
 use macroquad::math::UVec2;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{gui::{gui_box::GuiBox, resources::UiResolvedRects}, prelude::{ColorData, ComponentLoader, Context, Transform, UVec2Data, Visible}};
+use crate::core::error::EngineError;
+use crate::scene::scene_loader::{insert_component, parse_component, save_component};
 
 #[derive(Debug, Clone)]
 pub struct GuiImage {
@@ -23,7 +25,7 @@ impl Default for GuiImage {
     }
 }
 
-#[derive(Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct GuiImageLoaderData {
     pub texture: Option<String>,
     pub col_row: UVec2Data,
@@ -34,9 +36,8 @@ pub struct GuiImageLoaderData {
 pub struct GuiImageLoader;
 
 impl ComponentLoader for GuiImageLoader {
-    fn load(&self, ctx: &mut crate::prelude::Context, entity: hecs::Entity, data: &serde_json::Value) {
-        let loader_data: GuiImageLoaderData = serde_json::from_value(data.clone())
-            .unwrap_or_default();
+    fn load(&self, ctx: &mut crate::prelude::Context, entity: hecs::Entity, data: &serde_json::Value) -> Result<(), EngineError> {
+        let loader_data: GuiImageLoaderData = parse_component("GuiImage", data)?;
 
         let component = GuiImage {
             texture: loader_data.texture,
@@ -50,7 +51,16 @@ impl ComponentLoader for GuiImageLoader {
             screen_space: true
         };
 
-        ctx.world.insert_one(entity, component).expect("Failed to insert GuiImage");
+        insert_component(ctx, entity, component)
+    }
+
+    fn save(&self, ctx: &crate::prelude::Context, entity: hecs::Entity) -> Option<serde_json::Value> {
+        save_component(ctx, entity, |component: &GuiImage| GuiImageLoaderData {
+            texture: component.texture.clone(),
+            col_row: component.col_row.into(),
+            tint: component.tint.into(),
+            screen_space: component.screen_space
+        })
     }
 }
 
@@ -1,7 +1,9 @@
 use hecs::Entity;
 use macroquad::prelude::*;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use crate::{gui::{font_component::FontComponent, gui_box::GuiBox, resources::UiResolvedRects}, prelude::{ColorData, ComponentLoader, Context, Vec2Data, Visible}};
+use crate::core::error::EngineError;
+use crate::scene::scene_loader::{insert_component, parse_component, save_component};
 
 #[derive(Debug, Clone)]
 pub struct GuiInputField {
@@ -40,7 +42,7 @@ impl Default for GuiInputField {
     }
 }
 
-#[derive(Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct GuiInputFieldLoaderData {
     pub text: String,
     pub is_focused: bool,
@@ -60,9 +62,8 @@ pub struct GuiInputFieldLoaderData {
 pub struct GuiInputFieldLoader;
 
 impl ComponentLoader for GuiInputFieldLoader {
-    fn load(&self, ctx: &mut crate::prelude::Context, entity: hecs::Entity, data: &serde_json::Value) {
-        let loader_data: GuiInputFieldLoaderData = serde_json::from_value(data.clone())
-            .unwrap_or_default();
+    fn load(&self, ctx: &mut crate::prelude::Context, entity: hecs::Entity, data: &serde_json::Value) -> Result<(), EngineError> {
+        let loader_data: GuiInputFieldLoaderData = parse_component("GuiInputField", data)?;
 
         let component = GuiInputField {
             text: loader_data.text,
@@ -85,7 +86,25 @@ impl ComponentLoader for GuiInputFieldLoader {
             right_key_repeat_timer: loader_data.right_key_repeat_timer
         };
 
-        ctx.world.insert_one(entity, component).expect("Failed to insert GuiInputField");
+        insert_component(ctx, entity, component)
+    }
+
+    fn save(&self, ctx: &crate::prelude::Context, entity: hecs::Entity) -> Option<serde_json::Value> {
+        save_component(ctx, entity, |component: &GuiInputField| GuiInputFieldLoaderData {
+            text: component.text.clone(),
+            is_focused: component.is_focused,
+            caret_blink_timer: component.caret_blink_timer,
+            caret_visible: component.caret_visible,
+            max_chars: component.max_chars,
+            font_size: component.font_size,
+            color: component.color.into(),
+            backspace_repeat_timer: component.backspace_repeat_timer,
+            padding: component.padding.into(),
+            caret_position: component.caret_position,
+            scroll_offset: component.scroll_offset,
+            left_key_repeat_timer: component.left_key_repeat_timer,
+            right_key_repeat_timer: component.right_key_repeat_timer
+        })
     }
 }
 
@@ -159,7 +178,7 @@ pub fn input_field_typing_system(ctx: &mut Context) {
     const KEY_REPEAT_RATE: f32 = 0.05;
 
     // --- MODIFIED: Get dt once ---
-    let dt = ctx.dt();
+    let dt = ctx.unscaled_dt();
     
     // --- MODIFIED: Get map once ---
     let resolved_rects_map = &ctx.resource::<UiResolvedRects>().0;
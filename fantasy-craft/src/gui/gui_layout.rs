@@ -2,16 +2,18 @@ use std::collections::HashSet;
 
 use hecs::Entity;
 use macroquad::prelude::*;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use crate::gui::gui_box::GuiBox;
 use crate::gui::gui_draggable::GuiDraggable;
 use crate::gui::gui_element::GuiElement;
 use crate::gui::gui_local_offset::GuiLocalOffset;
 use crate::gui::resources::UiResolvedRects;
-use crate::prelude::{ComponentLoader, Context, Parent, Transform};
+use crate::prelude::{ComponentLoader, Context, Local, Parent, Transform};
 use crate::gui::gui_dimension::{GuiDimension, GuiDimensionLoaderData};
+use crate::core::error::EngineError;
+use crate::scene::scene_loader::{insert_component, parse_component, save_component};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct GuiLayout {
     pub x: GuiDimension,
     pub y: GuiDimension
@@ -26,7 +28,7 @@ impl Default for GuiLayout {
     }
 }
 
-#[derive(Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct GuiLayoutLoaderData {
     #[serde(default)]
     pub x: GuiDimensionLoaderData,
@@ -37,9 +39,8 @@ pub struct GuiLayoutLoaderData {
 pub struct GuiLayoutLoader;
 
 impl ComponentLoader for GuiLayoutLoader {
-    fn load(&self, ctx: &mut crate::prelude::Context, entity: hecs::Entity, data: &serde_json::Value) {
-        let loader_data: GuiLayoutLoaderData = serde_json::from_value(data.clone())
-            .unwrap_or_default();
+    fn load(&self, ctx: &mut crate::prelude::Context, entity: hecs::Entity, data: &serde_json::Value) -> Result<(), EngineError> {
+        let loader_data: GuiLayoutLoaderData = parse_component("GuiLayout", data)?;
 
         let parse_dimension = |loader_dim: GuiDimensionLoaderData| -> GuiDimension {
             match loader_dim {
@@ -58,12 +59,53 @@ impl ComponentLoader for GuiLayoutLoader {
             y: parse_dimension(loader_data.y),
         };
 
-        ctx.world.insert_one(entity, component).expect("Failed to insert GuiLayout");
+        insert_component(ctx, entity, component)
     }
+
+    fn save(&self, ctx: &crate::prelude::Context, entity: hecs::Entity) -> Option<serde_json::Value> {
+        save_component(ctx, entity, |component: &GuiLayout| GuiLayoutLoaderData {
+            x: component.x.into(),
+            y: component.y.into()
+        })
+    }
+}
+
+/// Screen size used by the previous run of `gui_resolve_layout_system`.
+#[derive(Debug, Default)]
+pub struct GuiLayoutCache {
+    screen_size: Option<(f32, f32)>
+}
+
+/// True when a layout input changed since the last run: the screen size, a GUI component,
+/// a `Parent` link, or the `Transform` of a GUI entity (e.g. dragged).
+fn gui_layout_is_dirty(ctx: &Context, cache: &GuiLayoutCache, screen_size: (f32, f32)) -> bool {
+    if cache.screen_size != Some(screen_size) {
+        return true;
+    }
+
+    let structure_changed = !ctx.changed::<GuiBox>().is_empty()
+        || !ctx.removed::<GuiBox>().is_empty()
+        || !ctx.changed::<GuiElement>().is_empty()
+        || !ctx.removed::<GuiElement>().is_empty()
+        || !ctx.changed::<GuiLayout>().is_empty()
+        || !ctx.changed::<GuiLocalOffset>().is_empty()
+        || !ctx.changed::<Parent>().is_empty();
+
+    structure_changed || ctx.changed::<Transform>().into_iter().any(|entity| {
+        ctx.world.satisfies::<&GuiBox>(entity).unwrap_or(false)
+            || ctx.world.satisfies::<&GuiElement>(entity).unwrap_or(false)
+    })
 }
 
-pub fn gui_resolve_layout_system(ctx: &mut Context) {
-    let (screen_w, screen_h) = (screen_width(), screen_height());
+pub fn gui_resolve_layout_system(ctx: &mut Context, cache: &mut Local<GuiLayoutCache>) {
+    let screen = ctx.screen();
+    let (screen_w, screen_h) = (screen.width, screen.height);
+
+    // Les rectangles de la frame précédente restent valides si rien n'a changé
+    if !gui_layout_is_dirty(ctx, cache, (screen_w, screen_h)) {
+        return;
+    }
+    cache.screen_size = Some((screen_w, screen_h));
     
     ctx.resource_mut::<UiResolvedRects>().0.clear();
 
@@ -81,6 +123,7 @@ pub fn gui_resolve_layout_system(ctx: &mut Context) {
     let mut entities_to_process: Vec<Entity> = entities.into_iter().collect();
     
     let mut iterations = 0;
+    let mut moved = Vec::new();
     
     while !entities_to_process.is_empty() && iterations < 10 {
         // --- PHASE 1: COLLECTER LES MODIFICATIONS ---
@@ -168,12 +211,18 @@ pub fn gui_resolve_layout_system(ctx: &mut Context) {
                 .map_or(false, |d| d.is_dragging);
 
             if !is_dragging {
-                if let Ok(mut transform) = ctx.world.get::<&mut Transform>(entity) {
+                if let Ok(mut transform) = ctx.world.get::<&mut Transform>(entity)
+                    && transform.position != pos {
                     transform.position = pos;
+                    moved.push(entity);
                 }
             }
         }
         
         iterations += 1;
     }
+
+    for entity in moved {
+        ctx.mark_changed::<Transform>(entity);
+    }
 }
@@ -1,16 +1,18 @@
 use hecs::Entity;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::prelude::{ComponentLoader};
 use crate::gui::gui_dimension::{GuiDimension, GuiDimensionLoaderData};
+use crate::core::error::EngineError;
+use crate::scene::scene_loader::{insert_component, parse_component, save_component};
 
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
 pub struct GuiLocalOffset {
     pub x: GuiDimension,
     pub y: GuiDimension
 }
 
-#[derive(Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct GuiLocalOffsetLoaderData {
     pub x: GuiDimensionLoaderData,
     pub y: GuiDimensionLoaderData
@@ -19,9 +21,8 @@ pub struct GuiLocalOffsetLoaderData {
 pub struct GuiLocalOffsetLoader;
 
 impl ComponentLoader for GuiLocalOffsetLoader {
-    fn load(&self, ctx: &mut crate::prelude::Context, entity: Entity, data: &serde_json::Value) {
-        let loader_data: GuiLocalOffsetLoaderData = serde_json::from_value(data.clone())
-            .unwrap_or_default();
+    fn load(&self, ctx: &mut crate::prelude::Context, entity: Entity, data: &serde_json::Value) -> Result<(), EngineError> {
+        let loader_data: GuiLocalOffsetLoaderData = parse_component("GuiLocalOffset", data)?;
 
         let parse_dimension = |loader_dim: GuiDimensionLoaderData| -> GuiDimension {
             match loader_dim {
@@ -40,6 +41,13 @@ impl ComponentLoader for GuiLocalOffsetLoader {
             y: parse_dimension(loader_data.y)
         };
 
-        ctx.world.insert_one(entity, component).expect("Failed to insert GuiLocalOffset");
+        insert_component(ctx, entity, component)
+    }
+
+    fn save(&self, ctx: &crate::prelude::Context, entity: Entity) -> Option<serde_json::Value> {
+        save_component(ctx, entity, |component: &GuiLocalOffset| GuiLocalOffsetLoaderData {
+            x: component.x.into(),
+            y: component.y.into()
+        })
     }
 }
@@ -1,6 +1,8 @@
 use macroquad::prelude::*;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use crate::{gui::{gui_box::GuiBox, resources::UiResolvedRects}, prelude::{ColorData, ComponentLoader, Context, Visible}};
+use crate::core::error::EngineError;
+use crate::scene::scene_loader::{insert_component, parse_component, save_component};
 
 #[derive(Debug, Clone, Copy)]
 pub struct GuiSlider {
@@ -25,7 +27,7 @@ impl Default for GuiSlider {
     }
 }
 
-#[derive(Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct GuiSliderLoaderData {
     pub value: f32,
     pub min: f32,
@@ -38,9 +40,8 @@ pub struct GuiSliderLoaderData {
 pub struct GuiSliderLoader;
 
 impl ComponentLoader for GuiSliderLoader {
-    fn load(&self, ctx: &mut crate::prelude::Context, entity: hecs::Entity, data: &serde_json::Value) {
-        let loader_data: GuiSliderLoaderData = serde_json::from_value(data.clone())
-            .unwrap_or_default();
+    fn load(&self, ctx: &mut crate::prelude::Context, entity: hecs::Entity, data: &serde_json::Value) -> Result<(), EngineError> {
+        let loader_data: GuiSliderLoaderData = parse_component("GuiSlider", data)?;
 
         let component = GuiSlider {
             value: loader_data.value,
@@ -56,7 +57,18 @@ impl ComponentLoader for GuiSliderLoader {
             handle_width: loader_data.handle_width
         };
 
-        ctx.world.insert_one(entity, component).expect("Failed to insert GuiSliderData");
+        insert_component(ctx, entity, component)
+    }
+
+    fn save(&self, ctx: &crate::prelude::Context, entity: hecs::Entity) -> Option<serde_json::Value> {
+        save_component(ctx, entity, |component: &GuiSlider| GuiSliderLoaderData {
+            value: component.value,
+            min: component.min,
+            max: component.max,
+            is_dragging_handle: component.is_dragging_handle,
+            handle_color: component.handle_color.into(),
+            handle_width: component.handle_width
+        })
     }
 }
 
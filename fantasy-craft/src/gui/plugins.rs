@@ -2,11 +2,14 @@ use std::collections::HashMap;
 
 use macroquad::math::Vec2;
 
-use crate::{gui::{alignment::{HorizontalAlignmentLoader, VerticalAlignmentLoader}, gui_action::GuiActionLoader, gui_box::{GuiBoxLoader, gui_box_render_system}, gui_button::button_interaction_system, gui_checkbox::{GuiCheckboxLoader, checkbox_logic_system, checkbox_render_system}, gui_draggable::{GuiDraggableLoader, draggable_system}, gui_image::{GuiImageLoader, gui_image_render_system}, gui_input_field::{GuiInputFieldLoader, input_field_focus_system, input_field_render_system, input_field_typing_system}, gui_layout::{GuiLayoutLoader, gui_resolve_layout_system}, gui_local_offset::GuiLocalOffsetLoader, gui_slider::{GuiSliderLoader, slider_interaction_system, slider_render_system}, text_display::{TextDisplayLoader, text_render_system}}, prelude::{GameState, Plugin, Stage, System}};
+use crate::{gui::{alignment::{HorizontalAlignmentLoader, VerticalAlignmentLoader}, gui_action::GuiActionLoader, gui_box::{GuiBox, GuiBoxLoader, gui_box_removed_hook, gui_box_render_system}, gui_button::button_interaction_system, gui_checkbox::{GuiCheckboxLoader, checkbox_logic_system, checkbox_render_system}, gui_draggable::{GuiDraggableLoader, draggable_system}, gui_image::{GuiImageLoader, gui_image_render_system}, gui_input_field::{GuiInputFieldLoader, input_field_focus_system, input_field_render_system, input_field_typing_system}, gui_layout::{GuiLayout, GuiLayoutLoader, gui_resolve_layout_system}, gui_local_offset::{GuiLocalOffset, GuiLocalOffsetLoader}, gui_slider::{GuiSliderLoader, slider_interaction_system, slider_render_system}, text_display::{TextDisplayLoader, text_render_system}}, prelude::{GameState, Plugin, Stage, System, has_window}};
 use crate::gui::resources::{UiResolvedRects, PreviousMousePosition};
-use crate::gui::gui_element::GuiElementLoader;
+use crate::gui::gui_element::{GuiElement, GuiElementLoader};
 use crate::gui::font_component::FontComponentLoader;
 use crate::gui::gui_button::GuiButtonLoader;
+use crate::core::plugins::{CorePlugin, PluginId};
+use crate::input::plugin::InputPlugin;
+use crate::hierarchy::plugins::HierarchyPlugin;
 
 pub struct GuiPlugin;
 
@@ -15,6 +18,16 @@ impl Plugin for GuiPlugin {
         app.context.insert_resource(UiResolvedRects(HashMap::new()));
         app.context.insert_resource(PreviousMousePosition(Vec2::ZERO));
 
+        app.on_remove::<GuiBox>(gui_box_removed_hook);
+
+        // Inputs of `gui_resolve_layout_system`, which only runs again when one of them changed.
+        // `Parent` and `Transform` are tracked by the `HierarchyPlugin`.
+        app
+            .track_changes::<GuiBox>()
+            .track_changes::<GuiElement>()
+            .track_changes::<GuiLayout>()
+            .track_changes::<GuiLocalOffset>();
+
         app.scene_loader
             .register("GuiElement", Box::new(GuiElementLoader))
             .register("GuiLayout", Box::new(GuiLayoutLoader))
@@ -33,14 +46,14 @@ impl Plugin for GuiPlugin {
             .register("GuiAction", Box::new(GuiActionLoader));
 
         app
-            .add_system(Stage::Update, System::new(
+            .add_system(Stage::Update, System::with_local(
                 gui_resolve_layout_system,
                 vec![GameState::Playing, GameState::Menu]
             ))
             .add_system(Stage::Update, System::new(
                 button_interaction_system,
                 vec![GameState::Playing, GameState::Menu]
-            ))
+            ).run_if(has_window()))
             .add_system(Stage::Update, System::new(
                 checkbox_logic_system,
                 vec![GameState::Playing, GameState::Menu]
@@ -48,42 +61,46 @@ impl Plugin for GuiPlugin {
             .add_system(Stage::Update, System::new(
                 draggable_system,
                 vec![GameState::Playing, GameState::Menu]
-            ))
+            ).run_if(has_window()))
             .add_system(Stage::Update, System::new(
                 slider_interaction_system,
                 vec![GameState::Playing, GameState::Menu]
-            ))
+            ).run_if(has_window()))
             .add_system(Stage::Update, System::new(
                 input_field_focus_system,
                 vec![GameState::Playing, GameState::Menu]
-            ))
+            ).run_if(has_window()))
             .add_system(Stage::Update, System::new(
                 input_field_typing_system,
                 vec![GameState::Playing, GameState::Menu]
-            ))
+            ).run_if(has_window()))
             .add_system(Stage::GuiRender, System::new(
                 gui_box_render_system,
                 vec![GameState::Playing, GameState::Menu]
-            ))
+            ).run_if(has_window()))
             .add_system(Stage::GuiRender, System::new(
                 checkbox_render_system,
                 vec![GameState::Playing, GameState::Menu]
-            ))
+            ).run_if(has_window()))
             .add_system(Stage::GuiRender, System::new(
                 slider_render_system,
                 vec![GameState::Playing, GameState::Menu]
-            ))
+            ).run_if(has_window()))
             .add_system(Stage::GuiRender, System::new(
                 text_render_system,
                 vec![GameState::Playing, GameState::Menu]
-            ))
+            ).run_if(has_window()))
             .add_system(Stage::GuiRender, System::new(
                 input_field_render_system,
                 vec![GameState::Playing, GameState::Menu]
-            ))
+            ).run_if(has_window()))
             .add_system(Stage::GuiRender, System::new(
                 gui_image_render_system,
                 vec![GameState::Playing, GameState::Menu]
-            ));
+            ).run_if(has_window()));
+    }
+
+    fn dependencies(&self) -> Vec<PluginId> {
+        vec![PluginId::of::<CorePlugin>(), PluginId::of::<InputPlugin>(), PluginId::of::<HierarchyPlugin>()]
     }
 }
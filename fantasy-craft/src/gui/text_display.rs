@@ -1,7 +1,9 @@
 use macroquad::prelude::*;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{gui::{alignment::{HorizontalAlignment, HorizontalAlignmentType, VerticalAlignment, VerticalAlignmentType}, font_component::FontComponent}, prelude::{ColorData, ComponentLoader, Context, Transform, Visible}};
+use crate::core::error::EngineError;
+use crate::scene::scene_loader::{insert_component, parse_component, save_component};
 
 #[derive(Debug, Clone)]
 pub struct TextDisplay {
@@ -22,7 +24,7 @@ impl Default for TextDisplay {
     }
 }
 
-#[derive(Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct TextDisplayLoaderData {
     pub text: String,
     pub font_size: f32,
@@ -33,9 +35,8 @@ pub struct TextDisplayLoaderData {
 pub struct TextDisplayLoader;
 
 impl ComponentLoader for TextDisplayLoader {
-    fn load(&self, ctx: &mut crate::prelude::Context, entity: hecs::Entity, data: &serde_json::Value) {
-        let loader_data: TextDisplayLoaderData = serde_json::from_value(data.clone())
-            .unwrap_or_default();
+    fn load(&self, ctx: &mut crate::prelude::Context, entity: hecs::Entity, data: &serde_json::Value) -> Result<(), EngineError> {
+        let loader_data: TextDisplayLoaderData = parse_component("TextDisplay", data)?;
 
         let component = TextDisplay {
             text: loader_data.text,
@@ -49,7 +50,16 @@ impl ComponentLoader for TextDisplayLoader {
             screen_space: true
         };
 
-        ctx.world.insert_one(entity, component).expect("Failed to insert TextDisplay");
+        insert_component(ctx, entity, component)
+    }
+
+    fn save(&self, ctx: &crate::prelude::Context, entity: hecs::Entity) -> Option<serde_json::Value> {
+        save_component(ctx, entity, |component: &TextDisplay| TextDisplayLoaderData {
+            text: component.text.clone(),
+            font_size: component.font_size,
+            color: component.color.into(),
+            screen_space: component.screen_space
+        })
     }
 }
 
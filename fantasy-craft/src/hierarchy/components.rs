@@ -1,11 +1,31 @@
 use hecs::{Entity, World};
 use macroquad::math::{Vec2, vec2};
 
+use crate::scene::entity_ref::{EntityRef, resolve_entity_ref};
 use crate::scene::scene_loader::ComponentLoader;
+use crate::core::error::EngineError;
+use crate::scene::scene_loader::{insert_component, parse_component, save_component};
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Parent(pub Entity);
 
+/// Reads `"@id"`, or the bare id of older scene files. The `SceneSaver` writes `Parent` itself,
+/// so that links to entities without a scene id are kept.
+pub struct ParentLoader;
+
+impl ComponentLoader for ParentLoader {
+    fn load(&self, ctx: &mut crate::prelude::Context, entity: Entity, data: &serde_json::Value) -> Result<(), EngineError> {
+        let target_id: String = parse_component("Parent", data)?;
+        let target = EntityRef::parse(&target_id).unwrap_or_else(|| EntityRef::new(&target_id));
+
+        resolve_entity_ref(ctx, entity, target, |ctx, entity, parent| {
+            insert_component(ctx, entity, Parent(parent))
+        });
+
+        Ok(())
+    }
+}
+
 pub fn find_children(world: &World, parent_id: Entity) -> Vec<Entity> {
     world.query::<&Parent>()
         .iter()
@@ -25,12 +45,15 @@ pub struct LocalOffset(pub Vec2);
 pub struct LocalOffsetLoader;
 
 impl ComponentLoader for LocalOffsetLoader {
-    fn load(&self, ctx: &mut crate::prelude::Context, entity: Entity, data: &serde_json::Value) {
-        let (x, y): (f32, f32) = serde_json::from_value(data.clone())
-            .unwrap_or((0.0, 0.0));
+    fn load(&self, ctx: &mut crate::prelude::Context, entity: Entity, data: &serde_json::Value) -> Result<(), EngineError> {
+        let (x, y): (f32, f32) = parse_component("LocalOffset", data)?;
 
         let component = LocalOffset(vec2(x, y));
 
-        ctx.world.insert_one(entity, component).expect("Failed to insert LocalOffset");
+        insert_component(ctx, entity, component)
+    }
+
+    fn save(&self, ctx: &crate::prelude::Context, entity: Entity) -> Option<serde_json::Value> {
+        save_component(ctx, entity, |component: &LocalOffset| (component.0.x, component.0.y))
     }
 }
@@ -1,20 +1,27 @@
-use crate::{hierarchy::systems::{hierarchy_transform_update_system, hierarchy_visible_update_system}, prelude::{GameState, LocalOffsetLoader, Plugin, Stage, System}};
+use crate::{hierarchy::systems::{hierarchy_transform_update_system, hierarchy_visible_update_system, parent_added_hook}, prelude::{GameState, LocalOffset, LocalOffsetLoader, LocalVisible, Parent, ParentLoader, Plugin, Stage, System, Transform, Visible}};
 
 pub struct HierarchyPlugin;
 
 impl Plugin for HierarchyPlugin {
     fn build(&self, app: &mut crate::prelude::App) {
         app.scene_loader
+            .register("Parent", Box::new(ParentLoader))
             .register("LocalOffset", Box::new(LocalOffsetLoader));
 
         app
+            .on_add::<Parent>(parent_added_hook)
+            .track_changes::<Transform>()
+            .track_changes::<LocalOffset>()
+            .track_changes::<Parent>()
+            .track_changes::<Visible>()
+            .track_changes::<LocalVisible>()
             .add_system(Stage::PostUpdate, System::new(
                 hierarchy_transform_update_system,
                 vec![GameState::Playing, GameState::Menu]
-            ))
+            ).label("hierarchy_transform"))
             .add_system(Stage::PostUpdate, System::new(
                 hierarchy_visible_update_system,
                 vec![GameState::Playing, GameState::Menu]
-            ));
+            ).label("hierarchy_visible"));
     }
 }
@@ -1,64 +1,96 @@
-use std::collections::HashMap;
+use std::collections::HashSet;
+
+use hecs::Entity;
+use macroquad::math::Vec2;
 
 use crate::prelude::{Context, LocalOffset, LocalVisible, Parent, Transform, Visible};
 
+// Levels of children updated in one run, guards against `Parent` cycles
+const MAX_HIERARCHY_DEPTH: usize = 64;
+
+/// Moves children with their parent. Only the children whose parent `Transform`,
+/// own `Transform`, `LocalOffset` or `Parent` changed since the last run are updated,
+/// then their own children, level by level.
 pub fn hierarchy_transform_update_system(ctx: &mut Context) {
-    let mut world_position = HashMap::new();
-    for (entity, transform) in ctx.world.query::<&Transform>().iter() {
-        world_position.insert(entity, transform.position);
-    }
+    let mut moved: HashSet<Entity> = ctx.changed::<Transform>().into_iter().collect();
+    let mut first_pass = true;
+
+    for _ in 0..MAX_HIERARCHY_DEPTH {
+        let updates: Vec<(Entity, Vec2)> = ctx.world.query::<(&Parent, &LocalOffset)>()
+            .iter()
+            .filter(|(entity, (parent, _))| moved.contains(&parent.0)
+                || (first_pass && (moved.contains(entity)
+                    || ctx.is_changed::<LocalOffset>(*entity)
+                    || ctx.is_changed::<Parent>(*entity))))
+            .filter_map(|(entity, (parent, local_offset))| {
+                let parent_transform = ctx.world.get::<&Transform>(parent.0).ok()?;
+                Some((entity, parent_transform.position + local_offset.0))
+            })
+            .collect();
 
-    for (_, (child_transform, parent, local_offset)) in ctx.world.query::<(&mut Transform, &Parent, &LocalOffset)>().iter() {
-        if let Some(&parent_world_pos) = world_position.get(&parent.0) {
-            child_transform.position = parent_world_pos + local_offset.0;
+        if updates.is_empty() {
+            break;
         }
+
+        moved.clear();
+        for (entity, position) in updates {
+            if let Ok(transform) = ctx.world.query_one_mut::<&mut Transform>(entity) {
+                transform.position = position;
+                ctx.mark_changed::<Transform>(entity);
+                moved.insert(entity);
+            }
+        }
+        first_pass = false;
     }
 }
 
-pub fn hierarchy_visible_update_system(ctx: &mut Context) {
-    
-    // --- PASSE 0.A: Initialisation de `Visible` ---
-    // On cherche les entités qui ont `Parent` MAIS n'ont PAS `Visible`
-    let mut entities_to_add_visible = Vec::new();
-    for (entity, _) in ctx.world.query::<&Parent>().without::<&Visible>().iter() {
-        entities_to_add_visible.push(entity);
-    }
-    
-    // On ajoute le composant `Visible` par défaut (true)
-    for entity in entities_to_add_visible {
-        ctx.world.insert_one(entity, Visible(true))
+/// Hook `on_add` de `Parent` : un enfant reçoit `Visible` et `LocalVisible` (true) dès qu'il est rattaché,
+/// s'il ne les a pas déjà.
+pub fn parent_added_hook(ctx: &mut Context, entity: Entity) {
+    if !ctx.world.satisfies::<&Visible>(entity).unwrap_or(false) {
+        ctx.insert_one(entity, Visible(true))
             .expect("Failed to add Visible component");
     }
 
-    // --- PASSE 0.B: Initialisation de `LocalVisible` ---
-    // On cherche les entités qui ont `Parent` MAIS n'ont PAS `LocalVisible`
-    let mut entities_to_add_local = Vec::new();
-    for (entity, _) in ctx.world.query::<&Parent>().without::<&LocalVisible>().iter() {
-        entities_to_add_local.push(entity);
-    }
-    
-    // On ajoute le composant `LocalVisible` par défaut (true)
-    for entity in entities_to_add_local {
-        ctx.world.insert_one(entity, LocalVisible(true))
+    if !ctx.world.satisfies::<&LocalVisible>(entity).unwrap_or(false) {
+        ctx.insert_one(entity, LocalVisible(true))
             .expect("Failed to add LocalVisible component");
     }
+}
 
-    // --- PASSE 1: Cacher l'état de visibilité de tous les parents ---
-    // (Votre code original)
-    let mut world_visibility = HashMap::new();
-    for (entity, visible) in ctx.world.query::<&Visible>().iter() {
-        world_visibility.insert(entity, visible.0);
-    }
+pub fn hierarchy_visible_update_system(ctx: &mut Context) {
+    // Seuls les enfants dont le parent, ou eux-mêmes, ont changé depuis le dernier passage
+    let mut changed: HashSet<Entity> = ctx.changed::<Visible>().into_iter()
+        .chain(ctx.changed::<LocalVisible>())
+        .chain(ctx.changed::<Parent>())
+        .collect();
 
-    for (_, (parent, local_visible, child_visible)) in ctx.world.query::<(&Parent, &LocalVisible, &mut Visible)>().iter() {
-        
-        // Obtenir la visibilité du parent (par défaut `true` s'il n'a pas de composant)
-        let parent_is_visible = world_visibility.get(&parent.0).copied().unwrap_or(true);
+    // Un niveau d'enfants par passe : les petits-enfants suivent leur parent mis à jour
+    for _ in 0..MAX_HIERARCHY_DEPTH {
+        let updates: Vec<(Entity, bool)> = ctx.world.query::<(&Parent, &LocalVisible, &Visible)>()
+            .iter()
+            .filter(|(entity, (parent, _, _))| changed.contains(entity) || changed.contains(&parent.0))
+            .filter_map(|(entity, (parent, local_visible, child_visible))| {
+                // Visibilité du parent (par défaut `true` s'il n'a pas de composant)
+                let parent_is_visible = ctx.world.get::<&Visible>(parent.0).map_or(true, |visible| visible.0);
 
-        // Obtenir l'état local de l'enfant
-        let child_local_is_visible = local_visible.0;
-        
-        // L'enfant est visible SI le parent l'est ET SI il l'est localement
-        child_visible.0 = parent_is_visible && child_local_is_visible;
+                // L'enfant est visible SI le parent l'est ET SI il l'est localement
+                let is_visible = parent_is_visible && local_visible.0;
+                (is_visible != child_visible.0).then_some((entity, is_visible))
+            })
+            .collect();
+
+        if updates.is_empty() {
+            break;
+        }
+
+        changed.clear();
+        for (entity, is_visible) in updates {
+            if let Ok(visible) = ctx.world.query_one_mut::<&mut Visible>(entity) {
+                visible.0 = is_visible;
+                ctx.mark_changed::<Visible>(entity);
+                changed.insert(entity);
+            }
+        }
     }
 }
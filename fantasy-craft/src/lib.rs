@@ -8,4 +8,6 @@ pub mod gui;
 pub mod hierarchy;
 pub mod scene;
 pub mod audio;
-pub mod input;
\ No newline at end of file
+pub mod input;
+pub mod profiler;
+pub mod timer;
\ No newline at end of file
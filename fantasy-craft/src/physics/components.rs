@@ -1,23 +1,37 @@
 use macroquad::prelude::*;
 use parry2d::shape::{SharedShape, Cuboid};
 use parry2d::na::Vector2;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::scene::scene_loader::ComponentLoader;
+use crate::core::error::EngineError;
+use crate::scene::scene_loader::{insert_component, parse_component, save_component};
 
-#[derive(Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct Vec2Data {
     pub x: f32,
     pub y: f32
 }
 
-#[derive(Deserialize, Debug, Default)]
+impl From<Vec2> for Vec2Data {
+    fn from(value: Vec2) -> Self {
+        Self { x: value.x, y: value.y }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct UVec2Data {
     pub x: u32,
     pub y: u32
 }
 
-#[derive(Debug, Clone, Copy)]
+impl From<UVec2> for UVec2Data {
+    fn from(value: UVec2) -> Self {
+        Self { x: value.x, y: value.y }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 #[allow(dead_code)]
 pub struct Transform {
     pub position: Vec2,
@@ -35,7 +49,7 @@ impl Default for Transform {
     }
 }
 
-#[derive(Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct TransformLoaderData {
     #[serde(default)]
     pub position: Vec2Data,
@@ -50,9 +64,8 @@ pub struct TransformLoaderData {
 pub struct TransformLoader;
 
 impl ComponentLoader for TransformLoader {
-    fn load(&self, ctx: &mut crate::prelude::Context, entity: hecs::Entity, data: &serde_json::Value) {
-        let loader_data: TransformLoaderData = serde_json::from_value(data.clone())
-            .unwrap_or_default();
+    fn load(&self, ctx: &mut crate::prelude::Context, entity: hecs::Entity, data: &serde_json::Value) -> Result<(), EngineError> {
+        let loader_data: TransformLoaderData = parse_component("Transform", data)?;
 
         let component = Transform {
             position: vec2(loader_data.position.x, loader_data.position.y),
@@ -60,7 +73,26 @@ impl ComponentLoader for TransformLoader {
             scale: vec2(loader_data.scale.x, loader_data.scale.y)
         };
 
-        ctx.world.insert_one(entity, component).expect("Failed to insert Transform");
+        insert_component(ctx, entity, component)
+    }
+
+    fn save(&self, ctx: &crate::prelude::Context, entity: hecs::Entity) -> Option<serde_json::Value> {
+        save_component(ctx, entity, |component: &Transform| TransformLoaderData {
+            position: component.position.into(),
+            rotation: component.rotation.into(),
+            scale: component.scale.into()
+        })
+    }
+}
+
+/// Position of a body at the start of the last fixed tick.
+/// Render systems blend it with `Transform` using `FixedTime::alpha`.
+#[derive(Debug, Clone, Copy)]
+pub struct PreviousPosition(pub Vec2);
+
+impl PreviousPosition {
+    pub fn interpolate(&self, current: Vec2, alpha: f32) -> Vec2 {
+        self.0.lerp(current, alpha)
     }
 }
 
@@ -72,6 +104,16 @@ pub enum BodyType {
     Kinematic
 }
 
+impl BodyType {
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            BodyType::Static => "static",
+            BodyType::Dynamic => "dynamic",
+            BodyType::Kinematic => "kinematic"
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct RigidBody {
     pub body_type: BodyType
@@ -85,7 +127,7 @@ impl RigidBody {
     }
 }
 
-#[derive(Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct RigidBodyLoaderData {
     pub body_type: String
 }
@@ -93,25 +135,30 @@ pub struct RigidBodyLoaderData {
 pub struct RigidBodyLoader;
 
 impl ComponentLoader for RigidBodyLoader {
-    fn load(&self, ctx: &mut crate::prelude::Context, entity: hecs::Entity, data: &serde_json::Value) {
-        let loader_data: RigidBodyLoaderData = serde_json::from_value(data.clone())
-            .unwrap_or_default();
+    fn load(&self, ctx: &mut crate::prelude::Context, entity: hecs::Entity, data: &serde_json::Value) -> Result<(), EngineError> {
+        let loader_data: RigidBodyLoaderData = parse_component("RigidBody", data)?;
 
         let body_type = match loader_data.body_type.as_str() {
             "static" => BodyType::Static,
             "dynamic" => BodyType::Dynamic,
             "kinematic" => BodyType::Kinematic,
-            _ => {
-                println!("Warning: Type de corps non reconnu '{}', utilisation de Dynamic par défaut.", loader_data.body_type);
-                BodyType::Dynamic
-            }
+            other => return Err(EngineError::component_data(
+                "RigidBody",
+                format!("unknown body type '{}', expected \"static\", \"dynamic\" or \"kinematic\"", other)
+            ))
         };
 
         let component = RigidBody {
             body_type
         };
 
-        ctx.world.insert_one(entity, component).expect("Failed to insert RigidBody");
+        insert_component(ctx, entity, component)
+    }
+
+    fn save(&self, ctx: &crate::prelude::Context, entity: hecs::Entity) -> Option<serde_json::Value> {
+        save_component(ctx, entity, |component: &RigidBody| RigidBodyLoaderData {
+            body_type: component.body_type.to_str().to_string()
+        })
     }
 }
 
@@ -131,7 +178,7 @@ impl Collider {
     }
 }
 
-#[derive(Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct ColliderLoaderData {
     pub shape: String,
     pub width: f32,
@@ -141,19 +188,25 @@ pub struct ColliderLoaderData {
 pub struct ColliderLoader;
 
 impl ComponentLoader for ColliderLoader {
-    fn load(&self, ctx: &mut crate::prelude::Context, entity: hecs::Entity, data: &serde_json::Value) {
-        let loader_data: ColliderLoaderData = serde_json::from_value(data.clone())
-            .unwrap_or_default();
-
-        match loader_data.shape.as_str() {
-            "Box" => {
-                ctx.world.insert_one(entity, Collider::new_box(loader_data.width, loader_data.height)).expect("Failed to insert Collider shape Box");
-            },
-            _ => {
-                println!("Warning: Type de shape non reconnu '{}', utilisation de Box par défaut.", loader_data.shape);
-                ctx.world.insert_one(entity, Collider::new_box(loader_data.width, loader_data.height)).expect("Failed to insert Collider shape Box");
-            }
+    fn load(&self, ctx: &mut crate::prelude::Context, entity: hecs::Entity, data: &serde_json::Value) -> Result<(), EngineError> {
+        let loader_data: ColliderLoaderData = parse_component("Collider", data)?;
+
+        if loader_data.shape != "Box" {
+            return Err(EngineError::component_data(
+                "Collider",
+                format!("unknown shape '{}', expected \"Box\"", loader_data.shape)
+            ));
         }
+
+        insert_component(ctx, entity, Collider::new_box(loader_data.width, loader_data.height))
+    }
+
+    fn save(&self, ctx: &crate::prelude::Context, entity: hecs::Entity) -> Option<serde_json::Value> {
+        save_component(ctx, entity, |component: &Collider| ColliderLoaderData {
+            shape: "Box".to_string(),
+            width: component.half_extents.x * 2.0,
+            height: component.half_extents.y * 2.0
+        })
     }
 }
 
@@ -163,13 +216,16 @@ pub struct Velocity(pub Vec2);
 pub struct VelocityLoader;
 
 impl ComponentLoader for VelocityLoader {
-    fn load(&self, ctx: &mut crate::prelude::Context, entity: hecs::Entity, data: &serde_json::Value) {
-        let loader_data: Vec2Data = serde_json::from_value(data.clone())
-            .unwrap_or_default();
+    fn load(&self, ctx: &mut crate::prelude::Context, entity: hecs::Entity, data: &serde_json::Value) -> Result<(), EngineError> {
+        let loader_data: Vec2Data = parse_component("Velocity", data)?;
 
         let component = Velocity(vec2(loader_data.x, loader_data.y));
 
-        ctx.world.insert_one(entity, component).expect("Failed to insert Velocity");
+        insert_component(ctx, entity, component)
+    }
+
+    fn save(&self, ctx: &crate::prelude::Context, entity: hecs::Entity) -> Option<serde_json::Value> {
+        save_component(ctx, entity, |component: &Velocity| Vec2Data::from(component.0))
     }
 }
 
@@ -179,12 +235,51 @@ pub struct Speed(pub f32);
 pub struct SpeedLoader;
 
 impl ComponentLoader for SpeedLoader {
-    fn load(&self, ctx: &mut crate::prelude::Context, entity: hecs::Entity, data: &serde_json::Value) {
-        let loader_data: f32 = serde_json::from_value(data.clone())
-            .unwrap_or_default();
+    fn load(&self, ctx: &mut crate::prelude::Context, entity: hecs::Entity, data: &serde_json::Value) -> Result<(), EngineError> {
+        let loader_data: f32 = parse_component("Speed", data)?;
 
         let component = Speed(loader_data);
 
-        ctx.world.insert_one(entity, component).expect("Failed to insert Speed");
+        insert_component(ctx, entity, component)
+    }
+
+    fn save(&self, ctx: &crate::prelude::Context, entity: hecs::Entity) -> Option<serde_json::Value> {
+        save_component(ctx, entity, |component: &Speed| component.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hecs::World;
+    use serde_json::json;
+
+    use super::*;
+    use crate::prelude::{AssetServer, Context};
+
+    #[test]
+    fn unknown_body_type_and_shape_are_errors() {
+        let mut ctx = Context::new(World::new(), AssetServer::new());
+        let entity = ctx.spawn(());
+
+        let error = RigidBodyLoader.load(&mut ctx, entity, &json!({ "body_type": "floating" })).unwrap_err();
+        assert!(error.to_string().contains("unknown body type 'floating'"), "{}", error);
+
+        let error = ColliderLoader.load(&mut ctx, entity, &json!({ "shape": "Circle", "width": 8.0, "height": 8.0 })).unwrap_err();
+        assert!(error.to_string().contains("unknown shape 'Circle'"), "{}", error);
+
+        assert!(ctx.world.get::<&RigidBody>(entity).is_err());
+        assert!(ctx.world.get::<&Collider>(entity).is_err());
+    }
+
+    #[test]
+    fn known_body_type_and_shape_are_loaded() {
+        let mut ctx = Context::new(World::new(), AssetServer::new());
+        let entity = ctx.spawn(());
+
+        RigidBodyLoader.load(&mut ctx, entity, &json!({ "body_type": "kinematic" })).unwrap();
+        ColliderLoader.load(&mut ctx, entity, &json!({ "shape": "Box", "width": 8.0, "height": 4.0 })).unwrap();
+
+        assert!(matches!(ctx.world.get::<&RigidBody>(entity).unwrap().body_type, BodyType::Kinematic));
+        assert_eq!(ctx.world.get::<&Collider>(entity).unwrap().half_extents, vec2(4.0, 2.0));
     }
 }
@@ -1,7 +1,92 @@
+use hecs::Entity;
 use macroquad::prelude::*;
 use parry2d::na::{Isometry2, Vector2};
 
+use crate::core::context::Context;
+use crate::physics::components::{PreviousPosition, Transform};
+use crate::prelude::{LocalOffset, Parent};
+
 /// Helper to convert Transform + Collider to Isometry2 (used by Parry)
 pub fn make_isometry(position: Vec2) -> Isometry2<f32> {
     Isometry2::new(Vector2::new(position.x, position.y), 0.0)
-}
\ No newline at end of file
+}
+// Ancestors looked up by `interpolated_position`, guards against `Parent` cycles
+const MAX_INTERPOLATION_DEPTH: usize = 64;
+
+/// Position to draw `entity` at, `alpha` being `FixedTime::alpha`.
+///
+/// Bodies are interpolated between their last two fixed ticks. Children without a body follow the
+/// interpolated position of their closest interpolated ancestor plus their `LocalOffset`, so they stay
+/// attached to it between two ticks. `None` if `entity` has no `Transform`.
+pub fn interpolated_position(ctx: &Context, entity: Entity, alpha: f32) -> Option<Vec2> {
+    let mut current = entity;
+    let mut offset = Vec2::ZERO;
+
+    for _ in 0..MAX_INTERPOLATION_DEPTH {
+        let mut query = ctx.world.query_one::<(&Transform, Option<&PreviousPosition>, Option<(&Parent, &LocalOffset)>)>(current).ok()?;
+        let (transform, previous, parent) = query.get()?;
+
+        match (previous, parent) {
+            (Some(previous), _) => return Some(previous.interpolate(transform.position, alpha) + offset),
+            (None, Some((parent, local_offset))) if ctx.world.contains(parent.0) => {
+                offset += local_offset.0;
+                current = parent.0;
+            },
+            (None, _) => return Some(transform.position + offset)
+        }
+    }
+
+    ctx.world.get::<&Transform>(entity).ok().map(|transform| transform.position)
+}
+
+#[cfg(test)]
+mod tests {
+    use hecs::World;
+
+    use super::*;
+    use crate::physics::components::{BodyType, RigidBody};
+    use crate::prelude::AssetServer;
+
+    #[test]
+    fn children_of_a_body_follow_its_interpolated_position() {
+        let mut ctx = Context::new(World::new(), AssetServer::new());
+
+        // The last fixed tick moved the body from (0, 0) to (100, 0), the hierarchy moved its children
+        let body = ctx.world.spawn((
+            Transform { position: vec2(100.0, 0.0), ..Default::default() },
+            PreviousPosition(Vec2::ZERO),
+            RigidBody::new(BodyType::Dynamic)
+        ));
+        let hand = ctx.world.spawn((
+            Transform { position: vec2(110.0, 5.0), ..Default::default() },
+            Parent(body),
+            LocalOffset(vec2(10.0, 5.0))
+        ));
+        let item = ctx.world.spawn((
+            Transform { position: vec2(112.0, 5.0), ..Default::default() },
+            Parent(hand),
+            LocalOffset(vec2(2.0, 0.0))
+        ));
+
+        assert_eq!(interpolated_position(&ctx, body, 0.5), Some(vec2(50.0, 0.0)));
+        assert_eq!(interpolated_position(&ctx, hand, 0.5), Some(vec2(60.0, 5.0)));
+        assert_eq!(interpolated_position(&ctx, item, 0.5), Some(vec2(62.0, 5.0)));
+        assert_eq!(interpolated_position(&ctx, item, 1.0), Some(vec2(112.0, 5.0)));
+    }
+
+    #[test]
+    fn entities_outside_of_a_body_keep_their_position() {
+        let mut ctx = Context::new(World::new(), AssetServer::new());
+
+        let root = ctx.world.spawn((Transform { position: vec2(20.0, 20.0), ..Default::default() },));
+        let child = ctx.world.spawn((
+            Transform { position: vec2(25.0, 20.0), ..Default::default() },
+            Parent(root),
+            LocalOffset(vec2(5.0, 0.0))
+        ));
+        let no_transform = ctx.world.spawn(());
+
+        assert_eq!(interpolated_position(&ctx, child, 0.5), Some(vec2(25.0, 20.0)));
+        assert_eq!(interpolated_position(&ctx, no_transform, 0.5), None);
+    }
+}
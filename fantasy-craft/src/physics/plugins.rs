@@ -1,4 +1,5 @@
-use crate::{core::plugins::Plugin, physics::systems::physics_system, prelude::{ColliderLoader, GameState, RigidBodyLoader, SpeedLoader, Stage, System, TransformLoader, VelocityLoader, movement_system}};
+use crate::{core::plugins::Plugin, physics::systems::{physics_system, store_previous_positions_system}, prelude::{ColliderLoader, GameState, RigidBodyLoader, SpeedLoader, Stage, System, TransformLoader, VelocityLoader, movement_system}};
+use crate::core::plugins::{CorePlugin, PluginId};
 
 pub struct PhysicsPlugin;
 
@@ -12,13 +13,21 @@ impl Plugin for PhysicsPlugin {
             .register("Speed", Box::new(SpeedLoader));
 
         app
-            .add_system(Stage::Update, System::new(
+            .add_system(Stage::FixedUpdate, System::new(
+                store_previous_positions_system,
+                vec![GameState::Playing]
+            ).label("store_previous_positions"))
+            .add_system(Stage::FixedUpdate, System::new(
                 movement_system,
                 vec![GameState::Playing]
-            ))
-            .add_system(Stage::PostUpdate, System::new(
+            ).label("movement").after("store_previous_positions"))
+            .add_system(Stage::FixedUpdate, System::new(
                 physics_system,
                 vec![GameState::Playing]
-            ));
+            ).label("physics").after("movement"));
+    }
+
+    fn dependencies(&self) -> Vec<PluginId> {
+        vec![PluginId::of::<CorePlugin>()]
     }
 }
@@ -5,22 +5,46 @@ use parry2d::query;
 use crate::core::event::EventBus;
 use crate::physics::components::Transform;
 use crate::core::context::Context;
-use crate::physics::components::{BodyType, Collider, RigidBody, Velocity, Speed};
+use crate::physics::components::{BodyType, Collider, PreviousPosition, RigidBody, Velocity, Speed};
 use crate::physics::helpers::make_isometry;
 use crate::prelude::CollisionEvent;
 
+/// Snapshots body positions before the fixed tick moves them, for render interpolation.
+pub fn store_previous_positions_system(ctx: &mut Context) {
+    let mut missing = Vec::new();
+
+    for (entity, (transform, previous)) in ctx.world.query_mut::<(&Transform, Option<&mut PreviousPosition>)>().with::<&RigidBody>() {
+        match previous {
+            Some(previous) => previous.0 = transform.position,
+            None => missing.push((entity, transform.position))
+        }
+    }
+
+    for (entity, position) in missing {
+        ctx.world.insert_one(entity, PreviousPosition(position))
+            .expect("Failed to insert PreviousPosition");
+    }
+}
+
 pub fn movement_system(ctx: &mut Context) {
     // Obtenez dt AVANT la boucle
     let dt = ctx.dt(); 
 
+    let mut moved = Vec::new();
+
     // query_mut() est plus idiomatique que query::<...>().iter() pour des &mut
-    for (_, (transform, velocity, speed)) in ctx.world.query_mut::<(&mut Transform, &mut Velocity, &Speed)>() {
+    for (entity, (transform, velocity, speed)) in ctx.world.query_mut::<(&mut Transform, &mut Velocity, &Speed)>() {
         if velocity.0.length() > 0.0 {
             velocity.0 = velocity.0.normalize();
+            moved.push(entity);
         }
         // Utilisez la variable dt locale
         transform.position += velocity.0 * speed.0 * dt;
     }
+
+    for entity in moved {
+        ctx.mark_changed::<Transform>(entity);
+    }
 }
 
 pub fn physics_system(ctx: &mut Context) {
@@ -104,11 +128,19 @@ pub fn physics_system(ctx: &mut Context) {
 
     // --- Phase 4: Write Back ---
     
+    let mut moved = Vec::new();
+
     for (entity, new_pos, _, _, _) in entities {
-        if let Ok(mut transform) = world.get::<&mut Transform>(entity) {
+        if let Ok(mut transform) = world.get::<&mut Transform>(entity)
+            && transform.position != new_pos {
             transform.position = new_pos;
+            moved.push(entity);
         }
     }
+
+    for entity in moved {
+        ctx.mark_changed::<Transform>(entity);
+    }
 }
 
 pub fn collider_debug_render_system(ctx: &mut Context) {
@@ -5,7 +5,15 @@ pub use crate::camera::camera2d::systems::*;
 pub use crate::camera::camera2d::plugins::*;
 pub use crate::utils::components::*;
 pub use crate::core::context::*;
+pub use crate::core::screen::*;
+pub use crate::core::rng::{Rng, RngStream};
+pub use crate::core::project::{ProjectSettings, WindowSettings};
+pub use crate::core::commands::Commands;
+pub use crate::core::hooks::ComponentHooks;
+pub use crate::core::change_detection::{ChangeTicks, ComponentTicks, Tick};
 pub use crate::core::schedule::*;
+pub use crate::core::condition::*;
+pub use crate::core::state::{GameState, StateId, StateMachine, StateTransition, States};
 pub use crate::core::app::*;
 pub use crate::physics::components::*;
 pub use crate::physics::helpers::*;
@@ -24,5 +32,14 @@ pub use crate::graphics::tiled_map::tiled_map::*;
 pub use crate::gui::plugins::*;
 pub use crate::gui::color_data::*;
 pub use crate::hierarchy::components::*;
-pub use crate::scene::scene_format::{EntityData, SceneFile};
-pub use crate::scene::scene_loader::{SceneLoader, ComponentLoader};
+pub use crate::profiler::plugins::*;
+pub use crate::profiler::resources::Profiler;
+pub use crate::timer::components::*;
+pub use crate::timer::event::*;
+pub use crate::timer::plugins::*;
+pub use crate::scene::scene_format::{EntityData, PrefabData, SceneFile};
+pub use crate::scene::scene_loader::{SceneLoader, ComponentLoader, SceneId, SpawnedScene, spawn_scene, parse_component, insert_component, save_component, save_marker};
+pub use crate::scene::scene_saver::SceneSaver;
+pub use crate::scene::scene_manager::{Persistent, PersistentLoader, SceneManager};
+pub use crate::scene::entity_ref::{EntityRef, resolve_entity_ref};
+pub use crate::core::error::{EngineError, ErrorReport};
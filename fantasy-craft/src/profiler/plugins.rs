@@ -0,0 +1,15 @@
+use crate::prelude::{App, Plugin, Stage, System, has_window};
+use crate::profiler::resources::Profiler;
+use crate::profiler::systems::{profiler_hotkeys_system, profiler_overlay_system};
+
+pub struct ProfilerPlugin;
+
+impl Plugin for ProfilerPlugin {
+    fn build(&self, app: &mut App) {
+        app.context.insert_resource(Profiler::default());
+
+        app
+            .add_system(Stage::Update, System::always(profiler_hotkeys_system).run_if(has_window()))
+            .add_system(Stage::GuiRender, System::always(profiler_overlay_system).run_if(has_window()));
+    }
+}
@@ -0,0 +1,158 @@
+use std::collections::VecDeque;
+use macroquad::miniquad::date;
+use serde_json::json;
+
+/// Wall time of one system run, in seconds since the profiler was created.
+#[derive(Debug, Clone)]
+pub struct SystemProfile {
+    pub name: String,
+    pub start: f64,
+    pub duration: f64
+}
+
+/// One `Schedule::run_stage` call. A stage can appear several times in a frame (FixedUpdate substeps).
+#[derive(Debug, Clone)]
+pub struct StageProfile {
+    pub stage: String,
+    pub start: f64,
+    pub duration: f64,
+    pub systems: Vec<SystemProfile>
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FrameProfile {
+    pub index: u64,
+    pub start: f64,
+    pub duration: f64,
+    pub stages: Vec<StageProfile>,
+    pub entity_count: u32,
+    pub event_count: usize
+}
+
+/// Records per-stage and per-system timings of the last `capacity` frames.
+/// `Schedule::run_stage` only measures anything while this resource exists and `enabled` is set.
+/// Recording starts disabled, showing the overlay (F3 with the `ProfilerPlugin`) enables it.
+pub struct Profiler {
+    pub enabled: bool,
+    pub overlay_visible: bool,
+    capacity: usize,
+    origin: f64,
+    frames: VecDeque<FrameProfile>,
+    current: FrameProfile
+}
+
+impl Profiler {
+    pub fn new(capacity: usize) -> Self {
+        let origin = date::now();
+
+        Self {
+            enabled: false,
+            overlay_visible: false,
+            capacity: capacity.max(1),
+            origin,
+            frames: VecDeque::with_capacity(capacity),
+            current: FrameProfile::default()
+        }
+    }
+
+    /// Seconds elapsed since the profiler was created.
+    pub fn now(&self) -> f64 {
+        date::now() - self.origin
+    }
+
+    pub fn record_stage(&mut self, stage: StageProfile) {
+        self.current.stages.push(stage);
+    }
+
+    /// Closes the current frame and pushes it in the ring buffer, dropping the oldest one when full.
+    pub fn end_frame(&mut self, entity_count: u32, event_count: usize) {
+        let now = self.now();
+        let index = self.current.index;
+
+        let mut frame = std::mem::replace(&mut self.current, FrameProfile {
+            index: index + 1,
+            start: now,
+            ..Default::default()
+        });
+        frame.duration = now - frame.start;
+        frame.entity_count = entity_count;
+        frame.event_count = event_count;
+
+        if self.frames.len() == self.capacity {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(frame);
+    }
+
+    pub fn frames(&self) -> impl Iterator<Item = &FrameProfile> {
+        self.frames.iter()
+    }
+
+    pub fn last_frame(&self) -> Option<&FrameProfile> {
+        self.frames.back()
+    }
+
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+
+    /// Builds a Chrome `trace_event` document, viewable in chrome://tracing or Perfetto.
+    pub fn to_chrome_trace(&self) -> serde_json::Value {
+        let to_us = |seconds: f64| seconds * 1_000_000.0;
+        let mut events = Vec::new();
+
+        for frame in &self.frames {
+            events.push(json!({
+                "name": format!("Frame {}", frame.index),
+                "cat": "frame",
+                "ph": "X",
+                "ts": to_us(frame.start),
+                "dur": to_us(frame.duration),
+                "pid": 0,
+                "tid": 0,
+                "args": {
+                    "entities": frame.entity_count,
+                    "events": frame.event_count
+                }
+            }));
+
+            for stage in &frame.stages {
+                events.push(json!({
+                    "name": stage.stage,
+                    "cat": "stage",
+                    "ph": "X",
+                    "ts": to_us(stage.start),
+                    "dur": to_us(stage.duration),
+                    "pid": 0,
+                    "tid": 0
+                }));
+
+                for system in &stage.systems {
+                    events.push(json!({
+                        "name": system.name,
+                        "cat": "system",
+                        "ph": "X",
+                        "ts": to_us(system.start),
+                        "dur": to_us(system.duration),
+                        "pid": 0,
+                        "tid": 0
+                    }));
+                }
+            }
+        }
+
+        json!({ "traceEvents": events, "displayTimeUnit": "ms" })
+    }
+
+    /// Writes the recorded frames to `path` as a Chrome trace. Not available on WASM (no file system).
+    pub fn export_chrome_trace(&self, path: &str) -> std::io::Result<()> {
+        let content = serde_json::to_string(&self.to_chrome_trace())?;
+        std::fs::write(path, content)
+    }
+}
+
+impl Default for Profiler {
+    fn default() -> Self {
+        Self::new(120)
+    }
+}
@@ -0,0 +1,72 @@
+use macroquad::prelude::*;
+use crate::prelude::Context;
+use crate::profiler::resources::Profiler;
+
+const TRACE_FILE: &str = "profiler_trace.json";
+const SLOWEST_SYSTEMS: usize = 8;
+
+/// F3 toggles the overlay and starts recording, F4 exports the recorded frames as a Chrome trace.
+pub fn profiler_hotkeys_system(ctx: &mut Context) {
+    let Some(profiler) = ctx.get_resource_mut::<Profiler>() else {
+        return;
+    };
+
+    if is_key_pressed(KeyCode::F3) {
+        profiler.overlay_visible = !profiler.overlay_visible;
+        profiler.enabled |= profiler.overlay_visible;
+    }
+
+    if is_key_pressed(KeyCode::F4) {
+        match profiler.export_chrome_trace(TRACE_FILE) {
+            Ok(()) => info!("Profiler: Trace exported to {}", TRACE_FILE),
+            Err(e) => error!("Profiler: Failed to export trace: {}", e)
+        }
+    }
+}
+
+pub fn profiler_overlay_system(ctx: &mut Context) {
+    let Some(profiler) = ctx.get_resource::<Profiler>() else {
+        return;
+    };
+
+    if !profiler.overlay_visible {
+        return;
+    }
+
+    let Some(frame) = profiler.last_frame() else {
+        return;
+    };
+
+    let mut lines = vec![
+        format!("Frame {}: {:.2} ms", frame.index, frame.duration * 1000.0),
+        format!("Entities: {}  Events: {}", frame.entity_count, frame.event_count)
+    ];
+
+    for stage in &frame.stages {
+        lines.push(format!("{}: {:.2} ms", stage.stage, stage.duration * 1000.0));
+    }
+
+    let mut systems: Vec<_> = frame.stages.iter()
+        .flat_map(|stage| stage.systems.iter())
+        .collect();
+    systems.sort_by(|a, b| b.duration.total_cmp(&a.duration));
+
+    lines.push(String::from("Slowest systems:"));
+    for system in systems.into_iter().take(SLOWEST_SYSTEMS) {
+        let short_name = system.name.rsplit("::").next().unwrap_or(&system.name);
+        lines.push(format!("  {}: {:.3} ms", short_name, system.duration * 1000.0));
+    }
+
+    let font_size = 16.0;
+    let line_height = 18.0;
+    let width = 320.0;
+    let height = lines.len() as f32 * line_height + 10.0;
+    let x = ctx.screen().width - width - 10.0;
+    let y = 10.0;
+
+    draw_rectangle(x, y, width, height, Color::new(0.0, 0.0, 0.0, 0.7));
+
+    for (i, line) in lines.iter().enumerate() {
+        draw_text(line, x + 8.0, y + 18.0 + i as f32 * line_height, font_size, WHITE);
+    }
+}
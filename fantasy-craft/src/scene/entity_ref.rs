@@ -0,0 +1,166 @@
+use std::fmt;
+
+use hecs::Entity;
+use macroquad::logging::warn;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::core::context::Context;
+use crate::core::error::EngineError;
+use crate::scene::scene_loader::SceneId;
+
+/// Reference to another entity of the scene, written `"@id"` in the component data.
+///
+/// Inside a prefab instance the id is looked up among the entities of the instance first,
+/// then in the enclosing scenes, like `Parent`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EntityRef(pub String);
+
+impl EntityRef {
+    pub fn new(id: &str) -> Self {
+        Self(id.to_string())
+    }
+
+    /// Reads `"@id"`, `None` for any other string.
+    pub fn parse(value: &str) -> Option<Self> {
+        value.strip_prefix('@')
+            .filter(|id| !id.is_empty())
+            .map(Self::new)
+    }
+
+    pub fn id(&self) -> &str {
+        &self.0
+    }
+
+    /// Reference to `entity` by the id it was loaded with, for `ComponentLoader::save`.
+    /// `None` if the entity was not loaded from a scene.
+    pub fn of(ctx: &Context, entity: Entity) -> Option<Self> {
+        ctx.world.get::<&SceneId>(entity)
+            .ok()
+            .map(|scene_id| Self(scene_id.0.clone()))
+    }
+}
+
+impl fmt::Display for EntityRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "@{}", self.0)
+    }
+}
+
+impl Serialize for EntityRef {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for EntityRef {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+
+        Self::parse(&value)
+            .ok_or_else(|| serde::de::Error::custom(format!("expected an entity reference \"@id\", got \"{}\"", value)))
+    }
+}
+
+type ApplyFn = Box<dyn FnOnce(&mut Context, Entity, Entity) -> Result<(), EngineError> + Send>;
+
+pub(crate) struct PendingEntityRef {
+    pub(crate) entity: Entity,
+    pub(crate) scope: String,
+    // Component whose loader requested the reference, for the error report
+    pub(crate) component: String,
+    pub(crate) target: EntityRef,
+    pub(crate) apply: ApplyFn
+}
+
+/// References requested by the component loaders, resolved by the `SceneLoader`
+/// once every entity of the scene tree is spawned.
+#[derive(Default)]
+pub(crate) struct EntityRefQueue {
+    // Prefab instance of the entity being loaded
+    scope: String,
+    // Component being loaded
+    component: String,
+    pending: Vec<PendingEntityRef>,
+    // Scene loads in progress, a loader may spawn a scene itself
+    loads: usize
+}
+
+// Position in the queue when a scene load started
+pub(crate) struct EntityRefFrame {
+    start: usize,
+    scope: String,
+    component: String
+}
+
+pub(crate) fn begin_entity_refs(ctx: &mut Context) -> EntityRefFrame {
+    if ctx.get_resource::<EntityRefQueue>().is_none() {
+        ctx.insert_resource(EntityRefQueue::default());
+    }
+
+    let queue = ctx.resource_mut::<EntityRefQueue>();
+    queue.loads += 1;
+
+    EntityRefFrame {
+        start: queue.pending.len(),
+        scope: queue.scope.clone(),
+        component: queue.component.clone()
+    }
+}
+
+pub(crate) fn set_entity_ref_scope(ctx: &mut Context, scope: &str) {
+    let queue = ctx.resource_mut::<EntityRefQueue>();
+    queue.scope.clear();
+    queue.scope.push_str(scope);
+}
+
+pub(crate) fn set_entity_ref_component(ctx: &mut Context, component: &str) {
+    let queue = ctx.resource_mut::<EntityRefQueue>();
+    queue.component.clear();
+    queue.component.push_str(component);
+}
+
+/// References requested since `frame` began, in request order.
+pub(crate) fn end_entity_refs(ctx: &mut Context, frame: EntityRefFrame) -> Vec<PendingEntityRef> {
+    let queue = ctx.resource_mut::<EntityRefQueue>();
+    queue.loads -= 1;
+    queue.scope = frame.scope;
+    queue.component = frame.component;
+    queue.pending.split_off(frame.start)
+}
+
+/// Runs `apply(ctx, entity, target_entity)` once `target` is resolved, for component loaders
+/// whose data points to another entity of the scene:
+///
+/// ```ignore
+/// fn load(&self, ctx: &mut Context, entity: Entity, data: &Value) -> Result<(), EngineError> {
+///     let target: EntityRef = parse_component("CameraFollow", data)?;
+///
+///     resolve_entity_ref(ctx, entity, target, |ctx, entity, target| {
+///         insert_component(ctx, entity, CameraFollow(target))
+///     });
+///     Ok(())
+/// }
+/// ```
+///
+/// Only meaningful inside `ComponentLoader::load`, the references are resolved at the end of the scene load.
+pub fn resolve_entity_ref(
+    ctx: &mut Context,
+    entity: Entity,
+    target: EntityRef,
+    apply: impl FnOnce(&mut Context, Entity, Entity) -> Result<(), EngineError> + Send + 'static
+) {
+    let Some(queue) = ctx.get_resource_mut::<EntityRefQueue>().filter(|queue| queue.loads > 0) else {
+        warn!("EntityRef: '{}' requested outside of a scene load, it is ignored", target);
+        return;
+    };
+
+    let scope = queue.scope.clone();
+    let component = queue.component.clone();
+    queue.pending.push(PendingEntityRef {
+        entity,
+        scope,
+        component,
+        target,
+        apply: Box::new(apply)
+    });
+}
@@ -1,2 +1,5 @@
 pub mod scene_format;
-pub mod scene_loader;
\ No newline at end of file
+pub mod scene_loader;
+pub mod scene_saver;
+pub mod scene_manager;
+pub mod entity_ref;
\ No newline at end of file
@@ -1,28 +1,44 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 pub struct EntityData {
     pub id: String,
 
+    // Sorted so that components load, and are saved, in a stable order
     #[serde(default)]
-    pub components: HashMap<String, Value>
+    pub components: BTreeMap<String, Value>
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 pub struct ImportData {
     pub import: String
 }
 
-#[derive(Deserialize, Debug)]
+/// Instance of a prefab scene. Its entities are spawned with their id prefixed by `as`
+/// (`"farmer2/root"`), so the same prefab can be instanced several times.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PrefabData {
+    pub prefab: String,
+
+    #[serde(rename = "as")]
+    pub instance: String,
+
+    // Components by prefab entity id, deep-merged over the prefab ones. `null` removes a component.
+    #[serde(default)]
+    pub overrides: BTreeMap<String, BTreeMap<String, Value>>
+}
+
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(untagged)]
 pub enum SceneEntry {
     Entity(EntityData),
-    Import(ImportData)
+    Import(ImportData),
+    Prefab(PrefabData)
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 pub struct SceneFile {
     pub entities: Vec<SceneEntry>
 }
@@ -1,20 +1,209 @@
 use macroquad::prelude::*;
-use hecs::Entity;
+use hecs::{Component, Entity};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
 use crate::core::context::Context;
-use crate::prelude::Parent;
+use crate::core::error::{EngineError, ErrorReport};
+use crate::prelude::{Parent, Transform};
 use crate::scene::scene_format::{SceneFile, SceneEntry};
-// We need to import the Error trait explicitly
-use std::error::Error;
-use async_recursion::async_recursion;
+use crate::scene::scene_saver::SceneSaver;
+use crate::scene::entity_ref::{PendingEntityRef, begin_entity_refs, end_entity_refs, set_entity_ref_component, set_entity_ref_scope};
 
 pub trait ComponentLoader: Send + Sync + 'static {
-    fn load(&self, ctx: &mut Context, entity: Entity, data: &Value);
+    fn load(&self, ctx: &mut Context, entity: Entity, data: &Value) -> Result<(), EngineError>;
+
+    /// Encodes the component of `entity` in the format `load` reads, `None` if the entity does not have it.
+    /// Components whose loader keeps this default are left out by the `SceneSaver`.
+    fn save(&self, _ctx: &Context, _entity: Entity) -> Option<Value> {
+        None
+    }
+}
+
+/// Id of the scene entry an entity was loaded from, kept so that saving the scene writes the same ids.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SceneId(pub String);
+
+/// Deserializes the scene data of a component.
+/// Bad data is reported as an `EngineError::ComponentData` instead of silently falling back to defaults.
+pub fn parse_component<T: DeserializeOwned>(component: &str, data: &Value) -> Result<T, EngineError> {
+    serde_json::from_value(data.clone())
+        .map_err(|e| EngineError::component_data(component, e))
+}
+
+/// Inserts a component built by a `ComponentLoader`.
+pub fn insert_component<C: Component>(ctx: &mut Context, entity: Entity, component: C) -> Result<(), EngineError> {
+    let name = std::any::type_name::<C>().rsplit("::").next().unwrap_or_default();
+
+    ctx.insert_one(entity, component)
+        .map_err(|e| EngineError::component_data(name, e))
+}
+
+/// Encodes the `C` of `entity` as the loader data built by `encode`, for `ComponentLoader::save`.
+pub fn save_component<C: Component, D: Serialize>(ctx: &Context, entity: Entity, encode: impl FnOnce(&C) -> D) -> Option<Value> {
+    let component = ctx.world.get::<&C>(entity).ok()?;
+
+    // Going through the text form keeps the short f32 notation (0.7 rather than 0.699999988079071)
+    serde_json::to_string(&encode(&component))
+        .and_then(|json| serde_json::from_str(&json))
+        .map_err(|e| warn!("SceneSaver: Cannot encode {}: {}", std::any::type_name::<C>(), e))
+        .ok()
+}
+
+/// `ComponentLoader::save` of marker components, written as `{}`.
+pub fn save_marker<C: Component>(ctx: &Context, entity: Entity) -> Option<Value> {
+    ctx.world.satisfies::<&C>(entity)
+        .unwrap_or(false)
+        .then(|| Value::Object(Default::default()))
+}
+
+/// Components by namespaced entity id, applied over the scene data of the entity.
+type Overrides = HashMap<String, BTreeMap<String, Value>>;
+
+// Shared by the files of one load: the root scene, its imports and its prefabs
+#[derive(Default)]
+pub(crate) struct SceneLoadState {
+    entity_map: HashMap<String, Entity>,
+    spawned: Vec<Entity>,
+    reused: HashSet<String>,
+    // Files being instanced, to stop import cycles
+    stack: Vec<String>
+}
+
+/// Parsed scene files by path, with the error of the ones that could not be read.
+pub type SceneFiles = HashMap<String, Result<Arc<SceneFile>, EngineError>>;
+
+/// Entities spawned by `SceneLoader::spawn_scene`.
+#[derive(Debug, Clone)]
+pub struct SpawnedScene {
+    pub root: Entity,
+    /// Spawned entities by scene id, namespaced like in the scene file for prefab instances.
+    pub entities: HashMap<String, Entity>
+}
+
+// Directory of a scene file, URL-safe, imports and prefabs are relative to it
+fn scene_dir(path: &str) -> &str {
+    match path.rfind('/') {
+        Some(last_slash_idx) => &path[0..=last_slash_idx],
+        None => ""
+    }
+}
+
+/// Reads the scene file at `path` and every file it imports or instances as a prefab.
+/// Each file is read once, the ones that fail keep their error.
+pub async fn fetch_scene_files(path: &str) -> SceneFiles {
+    let mut files = SceneFiles::new();
+    let mut pending = vec![path.to_string()];
+
+    while let Some(path) = pending.pop() {
+        if files.contains_key(&path) {
+            continue;
+        }
+
+        let result = match load_string(&path).await {
+            Ok(json_content) => serde_json::from_str::<SceneFile>(&json_content)
+                .map_err(|e| EngineError::parse(&path, e)),
+            Err(e) => Err(EngineError::io(&path, e))
+        };
+
+        if let Ok(scene) = &result {
+            let current_dir = scene_dir(&path);
+
+            for entry in &scene.entities {
+                match entry {
+                    SceneEntry::Import(import_data) => pending.push(format!("{}{}", current_dir, import_data.import)),
+                    SceneEntry::Prefab(prefab_data) => pending.push(format!("{}{}", current_dir, prefab_data.prefab)),
+                    SceneEntry::Entity(_) => {}
+                }
+            }
+        }
+
+        files.insert(path, result.map(Arc::new));
+    }
+
+    files
+}
+
+/// Deep-merges `patch` into `base`: objects are merged key by key, any other value replaces the base one.
+fn merge_value(base: &mut Value, patch: Value) {
+    match (base, patch) {
+        (Value::Object(base), Value::Object(patch)) => {
+            for (key, value) in patch {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_value(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        },
+        (base, patch) => *base = patch
+    }
+}
+
+fn merge_components(base: &mut BTreeMap<String, Value>, patch: BTreeMap<String, Value>) {
+    for (name, data) in patch {
+        match base.get_mut(&name) {
+            Some(existing) => merge_value(existing, data),
+            None => {
+                base.insert(name, data);
+            }
+        }
+    }
+}
+
+/// Looks `target` up from the innermost prefab instance of `scope` ("a/b/") out to the scene root,
+/// so prefab entities reference their siblings and can still reach the entities of the outer scene.
+fn resolve_id(entity_map: &HashMap<String, Entity>, scope: &str, target: &str) -> Option<Entity> {
+    let mut scope = scope;
+
+    loop {
+        if let Some(entity) = entity_map.get(&format!("{}{}", scope, target)) {
+            return Some(*entity);
+        }
+
+        if scope.is_empty() {
+            return None;
+        }
+
+        let parent_scope = &scope[..scope.len() - 1];
+        scope = match parent_scope.rfind('/') {
+            Some(idx) => &scope[..=idx],
+            None => ""
+        };
+    }
 }
 
+/// Spawns the scene cached under `id` from a system, see `SceneLoader::spawn_scene`.
+///
+/// ```ignore
+/// let farmer = spawn_scene(ctx, "npc_farmer", vec2(2000.0, 1980.0))?;
+/// ctx.insert_one(farmer.root, Persistent)?;
+/// ```
+pub fn spawn_scene(ctx: &mut Context, id: &str, position: Vec2) -> Result<SpawnedScene, EngineError> {
+    let loader = ctx.get_resource::<SceneLoader>()
+        .cloned()
+        .expect("SceneLoader resource missing, it is inserted when the App starts");
+
+    loader.spawn_scene(ctx, id, position)
+}
+
+/// Adds `error` to the `ErrorReport` resource, creating it if needed.
+fn report(ctx: &mut Context, error: EngineError) {
+    if ctx.get_resource::<ErrorReport>().is_none() {
+        ctx.insert_resource(ErrorReport::new());
+    }
+
+    ctx.resource_mut::<ErrorReport>().push(error);
+}
+
+/// Component loaders by scene name. Cloning it shares the loaders, the `App` keeps a copy
+/// in the resources so systems can spawn scenes, see `spawn_scene`.
+#[derive(Clone)]
 pub struct SceneLoader {
-    component_loaders: HashMap<String, Box<dyn ComponentLoader>>,
+    component_loaders: HashMap<String, Arc<dyn ComponentLoader>>,
 }
 
 impl SceneLoader {
@@ -25,83 +214,158 @@ impl SceneLoader {
     }
 
     pub fn register<S: Into<String>>(&mut self, name: S, loader: Box<dyn ComponentLoader>) -> &mut Self {
-        self.component_loaders.insert(name.into(), loader);
+        self.component_loaders.insert(name.into(), Arc::from(loader));
         self
     }
 
+    /// Registered loaders by scene component name.
+    pub fn loaders(&self) -> impl Iterator<Item = (&str, &dyn ComponentLoader)> {
+        self.component_loaders.iter().map(|(name, loader)| (name.as_str(), loader.as_ref()))
+    }
+
+    /// Writes the world back to the format read by this loader.
+    pub fn saver(&self) -> SceneSaver<'_> {
+        SceneSaver::new(self)
+    }
+
     // Public entry point
-    pub async fn load_scene_from_file(&self, path: &str, ctx: &mut Context) -> Result<(), Box<dyn Error>> {
-        let mut entity_map: HashMap<String, Entity> = HashMap::new();
-        let mut parent_queue: Vec<(Entity, String)> = Vec::new();
+    // Fails only if the root file cannot be read or parsed. Bad components and
+    // broken imports are added to the `ErrorReport` and the rest of the scene still loads.
+    // Returns the spawned entities, in file order.
+    pub async fn load_scene_from_file(&self, path: &str, ctx: &mut Context) -> Result<Vec<Entity>, EngineError> {
+        self.load_scene_reusing(path, ctx, HashMap::new()).await
+    }
 
+    /// Same as `load_scene_from_file`, the entries whose id is in `reused` are not spawned again:
+    /// references to them resolve to the given entity. Used by the `SceneManager` for `Persistent` entities.
+    pub async fn load_scene_reusing(&self, path: &str, ctx: &mut Context, reused: HashMap<String, Entity>) -> Result<Vec<Entity>, EngineError> {
         info!("SceneLoader: Starting load from root: {}", path);
 
-        self.load_scene_internal(path, ctx, &mut entity_map, &mut parent_queue).await?;
-
-        self.process_parent_queue(ctx, &entity_map, parent_queue);
+        let files = fetch_scene_files(path).await;
+        let state = self.instantiate(path, ctx, &files, reused)?;
 
         info!("SceneLoader: Loading complete.");
-        Ok(())
+        Ok(state.spawned)
+    }
+
+    /// Spawns the scene cached by the `AssetServer` under `id` (the `scenes` of the asset file),
+    /// without waiting for any file. The root entity, the first one without a `Parent`, is moved to
+    /// `position` and the other top-level entities keep their offset to it.
+    pub fn spawn_scene(&self, ctx: &mut Context, id: &str, position: Vec2) -> Result<SpawnedScene, EngineError> {
+        let path = ctx.asset_server.get_scene_path(id)
+            .ok_or_else(|| EngineError::missing_asset("scene", id))?
+            .to_string();
+
+        let files: SceneFiles = ctx.asset_server.scene_files()
+            .map(|(path, scene)| (path.to_string(), Ok(scene.clone())))
+            .collect();
+
+        let state = self.instantiate(&path, ctx, &files, HashMap::new())?;
+
+        let top_level: Vec<Entity> = state.spawned.iter()
+            .copied()
+            .filter(|entity| !ctx.world.satisfies::<&Parent>(*entity).unwrap_or(false))
+            .collect();
+
+        let root = *top_level.first()
+            .ok_or_else(|| EngineError::parse(&path, "the scene has no top-level entity"))?;
+
+        let root_position = ctx.world.get::<&Transform>(root).map_or(Vec2::ZERO, |transform| transform.position);
+        let offset = position - root_position;
+
+        for entity in top_level {
+            if let Ok(transform) = ctx.world.query_one_mut::<&mut Transform>(entity) {
+                transform.position += offset;
+                ctx.mark_changed::<Transform>(entity);
+            }
+        }
+
+        Ok(SpawnedScene {
+            root,
+            entities: state.entity_map
+        })
+    }
+
+    // Spawns the scene at `path` and everything it imports, the files being already read
+    pub(crate) fn instantiate(&self, path: &str, ctx: &mut Context, files: &SceneFiles, reused: HashMap<String, Entity>) -> Result<SceneLoadState, EngineError> {
+        let mut state = SceneLoadState {
+            reused: reused.keys().cloned().collect(),
+            entity_map: reused,
+            ..Default::default()
+        };
+
+        let frame = begin_entity_refs(ctx);
+        let result = self.instantiate_file(path, ctx, files, &mut state, "", &Overrides::new());
+        let entity_refs = end_entity_refs(ctx, frame);
+        result?;
+
+        // Every entity of the tree exists now, references can point anywhere in it
+        self.resolve_entity_refs(ctx, &state.entity_map, entity_refs);
+
+        for id in &state.reused {
+            state.entity_map.remove(id);
+        }
+
+        Ok(state)
     }
 
     // Internal recursive function
-    // We explicitly cast errors to Box<dyn Error> to satisfy the signature
-    #[async_recursion]
-    async fn load_scene_internal(
+    fn instantiate_file(
         &self,
         path: &str,
         ctx: &mut Context,
-        entity_map: &mut HashMap<String, Entity>,
-        parent_queue: &mut Vec<(Entity, String)>,
-    ) -> Result<(), Box<dyn Error>> { // <--- The return type we must strictly adhere to
-        
-        // 1. Load string using Macroquad's HTTP/FS abstraction
-        let json_content = load_string(path)
-            .await
-            .map_err(|e| {
-                error!("SceneLoader: Failed to load file '{}': {}", path, e);
-                // Explicitly box the macroquad error into the trait object
-                Box::new(e) as Box<dyn Error>
-            })?;
-
-        // 2. Parse JSON
-        let scene_data: SceneFile = serde_json::from_str(&json_content)
-            .map_err(|e| {
-                error!("SceneLoader: Failed to parse JSON in '{}': {}", path, e);
-                // Explicitly box the serde error into the trait object
-                Box::new(e) as Box<dyn Error>
-            })?;
-
-        // 3. Resolve current directory URL-safely
-        let current_dir = if let Some(last_slash_idx) = path.rfind('/') {
-            &path[0..=last_slash_idx]
-        } else {
-            ""
-        };
+        files: &SceneFiles,
+        state: &mut SceneLoadState,
+        scope: &str,
+        overrides: &Overrides,
+    ) -> Result<(), EngineError> {
+        let scene_data = files.get(path)
+            .cloned()
+            .unwrap_or_else(|| Err(EngineError::missing_asset("scene", path)))?;
+
+        if state.stack.iter().any(|loading| loading == path) {
+            return Err(EngineError::parse(path, "the scene imports itself"));
+        }
+        state.stack.push(path.to_string());
+
+        let current_dir = scene_dir(path);
 
-        for entry in scene_data.entities {
+        for entry in &scene_data.entities {
             match entry {
                 SceneEntry::Entity(entity_data) => {
-                    let entity = ctx.world.spawn(());
+                    // Inside a prefab instance, ids are namespaced by the instance name
+                    let entity_id = format!("{}{}", scope, entity_data.id);
+                    let mut components = entity_data.components.clone();
+
+                    if let Some(patch) = overrides.get(&entity_id) {
+                        merge_components(&mut components, patch.clone());
+                        components.retain(|_, data| !data.is_null());
+                    }
+
+                    // Kept alive from a previous scene, see `Persistent`
+                    if state.reused.contains(&entity_id) {
+                        continue;
+                    }
+
+                    let entity = ctx.spawn((SceneId(entity_id.clone()),));
+                    state.spawned.push(entity);
                     
-                    if entity_map.insert(entity_data.id.clone(), entity).is_some() {
-                        warn!("Warning: Duplicate entity ID found: '{}'. Overwriting.", entity_data.id);
+                    if state.entity_map.insert(entity_id.clone(), entity).is_some() {
+                        warn!("Warning: Duplicate entity ID found: '{}'. Overwriting.", entity_id);
                     }
 
-                    for (component_name, component_data) in entity_data.components {
-                        if component_name == "Parent" {
-                            if let Some(target_id) = component_data.as_str() {
-                                parent_queue.push((entity, target_id.to_string()));
-                            } else {
-                                warn!("Warning: 'Parent' target for entity {} is invalid.", entity_data.id);
-                            }
-                            continue;
-                        }
+                    set_entity_ref_scope(ctx, scope);
+
+                    for (component_name, component_data) in components {
+                        set_entity_ref_component(ctx, &component_name);
 
-                        if let Some(loader) = self.component_loaders.get(&component_name) {
-                            loader.load(ctx, entity, &component_data);
-                        } else {
-                            warn!("Warning: No component loader registered for '{}'", component_name);
+                        let result = match self.component_loaders.get(&component_name) {
+                            Some(loader) => loader.load(ctx, entity, &component_data),
+                            None => Err(EngineError::component_data(&component_name, "no component loader registered"))
+                        };
+
+                        if let Err(e) = result {
+                            report(ctx, e.with_entity(&entity_id));
                         }
                     }
                 }
@@ -111,38 +375,193 @@ impl SceneLoader {
 
                     info!("Importing sub-scene from: {}", import_path_str);
 
-                    // 4. Recursive call
-                    // Since the recursive function already returns Result<(), Box<dyn Error>>,
-                    // the ? operator works fine here.
-                    self.load_scene_internal(
+                    // A broken import is reported, the entities of the current file still load
+                    if let Err(e) = self.instantiate_file(
                         &import_path_str,
                         ctx,
-                        entity_map,
-                        parent_queue,
-                    ).await?;
+                        files,
+                        state,
+                        scope,
+                        overrides,
+                    ) {
+                        report(ctx, e);
+                    }
+                }
+
+                SceneEntry::Prefab(prefab_data) => {
+                    if prefab_data.instance.is_empty() || prefab_data.instance.contains('/') {
+                        report(ctx, EngineError::parse(path, format!("invalid prefab instance name '{}'", prefab_data.instance)));
+                        continue;
+                    }
+
+                    let prefab_path = format!("{}{}", current_dir, prefab_data.prefab);
+                    let prefab_scope = format!("{}{}/", scope, prefab_data.instance);
+
+                    info!("Instancing prefab {} as '{}'", prefab_path, prefab_scope.trim_end_matches('/'));
+
+                    // Overrides of an enclosing instance are applied last, so they win over these ones
+                    let mut prefab_overrides = overrides.clone();
+                    let mut overridden_ids = Vec::new();
+
+                    for (id, patch) in &prefab_data.overrides {
+                        let entity_id = format!("{}{}", prefab_scope, id);
+                        let mut patch = patch.clone();
+
+                        if let Some(outer) = prefab_overrides.remove(&entity_id) {
+                            merge_components(&mut patch, outer);
+                        }
+
+                        overridden_ids.push(entity_id.clone());
+                        prefab_overrides.insert(entity_id, patch);
+                    }
+
+                    if let Err(e) = self.instantiate_file(
+                        &prefab_path,
+                        ctx,
+                        files,
+                        state,
+                        &prefab_scope,
+                        &prefab_overrides,
+                    ) {
+                        report(ctx, e);
+                        continue;
+                    }
+
+                    for entity_id in overridden_ids.iter().filter(|id| !state.entity_map.contains_key(*id)) {
+                        warn!("Warning: Override for unknown prefab entity '{}' in {}", entity_id, path);
+                    }
                 }
             }
         }
 
+        state.stack.pop();
         Ok(())
     }
 
-    fn process_parent_queue(
+    fn resolve_entity_refs(
         &self,
         ctx: &mut Context,
         entity_map: &HashMap<String, Entity>,
-        parent_queue: Vec<(Entity, String)>,
+        entity_refs: Vec<PendingEntityRef>,
     ) {
-        for (entity, parent_id) in parent_queue {
-            if let Some(parent_entity) = entity_map.get(&parent_id) {
-                if ctx.world.contains(entity) {
-                    ctx.world
-                        .insert_one(entity, Parent(*parent_entity))
-                        .expect("Failed to add Parent component");
-                }
-            } else {
-                warn!("Warning: Parent entity not found with ID '{}' for child entity {:?}", parent_id, entity);
+        for PendingEntityRef { entity, scope, component, target, apply } in entity_refs {
+            if !ctx.world.contains(entity) {
+                continue;
+            }
+
+            let result = match resolve_id(entity_map, &scope, target.id()) {
+                Some(target_entity) => apply(ctx, entity, target_entity),
+                None => Err(EngineError::component_data(&component, format!("no entity with id '{}' in the scene", target.id())))
+            };
+
+            if let Err(e) = result {
+                let entity_id = ctx.world.get::<&SceneId>(entity).map(|scene_id| scene_id.0.clone()).unwrap_or_default();
+                report(ctx, e.with_entity(&entity_id));
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use hecs::World;
+    use serde_json::json;
+
+    use super::*;
+    use crate::prelude::{AssetServer, CameraFollow, CameraFollowLoader, TransformLoader};
+
+    #[test]
+    fn merge_value_merges_objects_and_replaces_the_rest() {
+        let mut base = json!({
+            "speed": 50.0,
+            "sprite": { "texture": "farmer.png", "frame": [0, 0] },
+            "tags": ["npc"]
+        });
+
+        merge_value(&mut base, json!({
+            "sprite": { "frame": [1, 2] },
+            "tags": ["npc", "quest"],
+            "dialog": "hello"
+        }));
+
+        assert_eq!(base, json!({
+            "speed": 50.0,
+            "sprite": { "texture": "farmer.png", "frame": [1, 2] },
+            "tags": ["npc", "quest"],
+            "dialog": "hello"
+        }));
+
+        merge_value(&mut base, json!(null));
+        assert_eq!(base, json!(null));
+    }
+
+    #[test]
+    fn merge_components_adds_missing_components() {
+        let mut base = BTreeMap::from([("Speed".to_string(), json!({ "value": 1.0, "max": 2.0 }))]);
+
+        merge_components(&mut base, BTreeMap::from([
+            ("Speed".to_string(), json!({ "value": 3.0 })),
+            ("Persistent".to_string(), json!({}))
+        ]));
+
+        assert_eq!(base["Speed"], json!({ "value": 3.0, "max": 2.0 }));
+        assert_eq!(base["Persistent"], json!({}));
+    }
+
+    #[test]
+    fn resolve_id_walks_out_of_nested_prefab_instances() {
+        let mut world = World::new();
+        let mut entity_map = HashMap::new();
+        let mut spawn = |id: &str| {
+            let entity = world.spawn(());
+            entity_map.insert(id.to_string(), entity);
+            entity
+        };
+
+        let player = spawn("player");
+        let root = spawn("farmer/root");
+        let hat = spawn("farmer/hat");
+        let inner_root = spawn("farmer/horse/root");
+
+        // Innermost instance first
+        assert_eq!(resolve_id(&entity_map, "farmer/horse/", "root"), Some(inner_root));
+        assert_eq!(resolve_id(&entity_map, "farmer/", "root"), Some(root));
+
+        // Then the enclosing instances and the scene root
+        assert_eq!(resolve_id(&entity_map, "farmer/horse/", "hat"), Some(hat));
+        assert_eq!(resolve_id(&entity_map, "farmer/horse/", "player"), Some(player));
+        assert_eq!(resolve_id(&entity_map, "", "player"), Some(player));
+
+        // A path into a nested instance from an outer scope
+        assert_eq!(resolve_id(&entity_map, "", "farmer/horse/root"), Some(inner_root));
+
+        assert_eq!(resolve_id(&entity_map, "farmer/horse/", "missing"), None);
+        assert_eq!(resolve_id(&entity_map, "", "hat"), None);
+    }
+
+    #[test]
+    fn dangling_entity_ref_is_reported() {
+        let mut loader = SceneLoader::new();
+        loader
+            .register("Transform", Box::new(TransformLoader))
+            .register("CameraFollow", Box::new(CameraFollowLoader));
+
+        let scene: SceneFile = serde_json::from_str(r#"{
+            "entities": [
+                { "id": "player", "components": { "Transform": {} } },
+                { "id": "camera", "components": { "CameraFollow": "@ghost" } }
+            ]
+        }"#).unwrap();
+        let files = SceneFiles::from([("main.json".to_string(), Ok(Arc::new(scene)))]);
+
+        let mut ctx = Context::new(World::new(), AssetServer::new());
+        let state = loader.instantiate("main.json", &mut ctx, &files, HashMap::new()).unwrap();
+
+        assert!(ctx.world.get::<&CameraFollow>(state.entity_map["camera"]).is_err());
+        assert_eq!(ctx.resource::<ErrorReport>().errors(), [EngineError::ComponentData {
+            entity: "camera".to_string(),
+            component: "CameraFollow".to_string(),
+            message: "no entity with id 'ghost' in the scene".to_string()
+        }]);
+    }
+}
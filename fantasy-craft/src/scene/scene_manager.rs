@@ -0,0 +1,269 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use hecs::Entity;
+use macroquad::prelude::*;
+use serde_json::Value;
+
+use crate::core::context::Context;
+use crate::core::error::EngineError;
+use crate::core::screen::ScreenInfo;
+use crate::prelude::Parent;
+use crate::scene::scene_loader::{ComponentLoader, SceneId, insert_component, save_marker};
+
+/// Keeps an entity, and its children, alive when the scene it was loaded from is unloaded.
+/// When a later scene has an entry with the same id, the kept entity is reused instead of spawning a copy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Persistent;
+
+pub struct PersistentLoader;
+
+impl ComponentLoader for PersistentLoader {
+    fn load(&self, ctx: &mut Context, entity: Entity, _data: &Value) -> Result<(), EngineError> {
+        insert_component(ctx, entity, Persistent)
+    }
+
+    fn save(&self, ctx: &Context, entity: Entity) -> Option<Value> {
+        save_marker::<Persistent>(ctx, entity)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum SceneRequest {
+    Load(String),
+    Unload(String),
+    Replace(String)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FadePhase {
+    Out,
+    In
+}
+
+#[derive(Debug, Clone)]
+struct SceneFade {
+    phase: FadePhase,
+    elapsed: f32,
+    duration: f32,
+    path: String
+}
+
+// Longest frame counted by the fade, so the slow frame of a scene load does not skip the fade-in
+const MAX_FADE_STEP: f32 = 1.0 / 30.0;
+
+/// Loads, unloads and replaces scenes while the game runs, and remembers which entities each scene spawned.
+///
+/// Requests are queued and run by `App::run` at the end of the frame, scene loading being asynchronous.
+/// `App::step` does not load files, so requests stay queued in headless mode.
+///
+/// ```ignore
+/// // Walking through the door of the house
+/// ctx.resource_mut::<SceneManager>().replace_with_fade("resources/scenes/house.json", 0.5);
+/// ```
+pub struct SceneManager {
+    scenes: Vec<(String, Vec<Entity>)>,
+    requests: VecDeque<(SceneRequest, Option<f32>)>,
+    fade: Option<SceneFade>,
+    pub fade_color: Color
+}
+
+impl Default for SceneManager {
+    fn default() -> Self {
+        Self {
+            scenes: Vec::new(),
+            requests: VecDeque::new(),
+            fade: None,
+            fade_color: BLACK
+        }
+    }
+}
+
+impl SceneManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the scene at `path` on top of the current ones.
+    pub fn load(&mut self, path: &str) {
+        self.requests.push_back((SceneRequest::Load(path.to_string()), None));
+    }
+
+    /// Despawns the entities loaded from `path`, except the `Persistent` ones.
+    pub fn unload(&mut self, path: &str) {
+        self.requests.push_back((SceneRequest::Unload(path.to_string()), None));
+    }
+
+    /// Unloads every loaded scene, then loads the one at `path`.
+    pub fn replace(&mut self, path: &str) {
+        self.requests.push_back((SceneRequest::Replace(path.to_string()), None));
+    }
+
+    /// Same as `replace`, the screen fading to `fade_color` in `duration` seconds before the
+    /// switch and back in the same time after it.
+    pub fn replace_with_fade(&mut self, path: &str, duration: f32) {
+        self.requests.push_back((SceneRequest::Replace(path.to_string()), Some(duration)));
+    }
+
+    pub fn is_loaded(&self, path: &str) -> bool {
+        self.scenes.iter().any(|(scene_path, _)| scene_path == path)
+    }
+
+    /// Paths of the loaded scenes, in load order.
+    pub fn loaded_scenes(&self) -> impl Iterator<Item = &str> {
+        self.scenes.iter().map(|(path, _)| path.as_str())
+    }
+
+    /// Entities spawned by the scene at `path`, empty if it is not loaded.
+    pub fn entities(&self, path: &str) -> &[Entity] {
+        self.scenes.iter()
+            .find(|(scene_path, _)| scene_path == path)
+            .map_or(&[], |(_, entities)| entities.as_slice())
+    }
+
+    /// True while requests are waiting or a fade is running.
+    pub fn is_transitioning(&self) -> bool {
+        !self.requests.is_empty() || self.fade.is_some()
+    }
+
+    /// Opacity of the fade overlay, 0 when no fade is running.
+    pub fn fade_alpha(&self) -> f32 {
+        match &self.fade {
+            Some(fade) => {
+                let progress = if fade.duration > 0.0 { (fade.elapsed / fade.duration).clamp(0.0, 1.0) } else { 1.0 };
+
+                match fade.phase {
+                    FadePhase::Out => progress,
+                    FadePhase::In => 1.0 - progress
+                }
+            },
+            None => 0.0
+        }
+    }
+
+    /// Records entities spawned by the scene at `path`. Loading a scene twice adds to the same entry.
+    pub(crate) fn track(&mut self, path: &str, entities: Vec<Entity>) {
+        match self.scenes.iter_mut().find(|(scene_path, _)| scene_path == path) {
+            Some((_, tracked)) => tracked.extend(entities),
+            None => self.scenes.push((path.to_string(), entities))
+        }
+    }
+
+    /// Forgets the scene at `path` and returns its entities.
+    pub(crate) fn untrack(&mut self, path: &str) -> Vec<Entity> {
+        match self.scenes.iter().position(|(scene_path, _)| scene_path == path) {
+            Some(index) => self.scenes.remove(index).1,
+            None => {
+                warn!("SceneManager: Cannot unload '{}', it is not loaded", path);
+                Vec::new()
+            }
+        }
+    }
+
+    /// Forgets every loaded scene and returns their entities.
+    pub(crate) fn untrack_all(&mut self) -> Vec<Entity> {
+        self.scenes.drain(..).flat_map(|(_, entities)| entities).collect()
+    }
+
+    pub(crate) fn update_fade(&mut self, dt: f32) {
+        if let Some(fade) = &mut self.fade {
+            fade.elapsed += dt.min(MAX_FADE_STEP);
+
+            if fade.phase == FadePhase::In && fade.elapsed >= fade.duration {
+                self.fade = None;
+            }
+        }
+    }
+
+    /// Next request to run now. A faded replace holds the queue until the screen is covered.
+    pub(crate) fn next_request(&mut self) -> Option<SceneRequest> {
+        if let Some(fade) = &mut self.fade
+            && fade.phase == FadePhase::Out {
+            if fade.elapsed < fade.duration {
+                return None;
+            }
+
+            fade.phase = FadePhase::In;
+            fade.elapsed = 0.0;
+            return Some(SceneRequest::Replace(fade.path.clone()));
+        }
+
+        match self.requests.pop_front()? {
+            (SceneRequest::Replace(path), Some(duration)) => {
+                self.fade = Some(SceneFade {
+                    phase: FadePhase::Out,
+                    elapsed: 0.0,
+                    duration,
+                    path
+                });
+                None
+            },
+            (request, _) => Some(request)
+        }
+    }
+
+    pub(crate) fn draw_fade(&self, screen: &ScreenInfo) {
+        let alpha = self.fade_alpha();
+
+        if alpha > 0.0 {
+            let color = Color::new(self.fade_color.r, self.fade_color.g, self.fade_color.b, self.fade_color.a * alpha);
+            draw_rectangle(0.0, 0.0, screen.width, screen.height, color);
+        }
+    }
+}
+
+/// True if `entity` or one of its ancestors is `Persistent`.
+pub(crate) fn is_persistent(ctx: &Context, entity: Entity) -> bool {
+    let mut current = Some(entity);
+    let mut visited = HashSet::new();
+
+    while let Some(entity) = current {
+        if !visited.insert(entity) {
+            break;
+        }
+
+        if ctx.world.satisfies::<&Persistent>(entity).unwrap_or(false) {
+            return true;
+        }
+
+        current = ctx.world.get::<&Parent>(entity).ok().map(|parent| parent.0);
+    }
+
+    false
+}
+
+/// Scene ids of the kept entities, reused by the next scene loads.
+pub(crate) fn persistent_ids(ctx: &Context) -> HashMap<String, Entity> {
+    ctx.world.query::<&SceneId>()
+        .iter()
+        .filter(|(entity, _)| is_persistent(ctx, *entity))
+        .map(|(entity, scene_id)| (scene_id.0.clone(), entity))
+        .collect()
+}
+
+/// Despawns `entities` and their children, except the `Persistent` ones.
+pub(crate) fn despawn_scene_entities(ctx: &mut Context, entities: Vec<Entity>) {
+    let mut children: HashMap<Entity, Vec<Entity>> = HashMap::new();
+    for (child, parent) in ctx.world.query::<&Parent>().iter() {
+        children.entry(parent.0).or_default().push(child);
+    }
+
+    let mut to_despawn = Vec::new();
+    let mut visited = HashSet::new();
+    let mut stack = entities;
+
+    while let Some(entity) = stack.pop() {
+        if !visited.insert(entity) || !ctx.world.contains(entity) || is_persistent(ctx, entity) {
+            continue;
+        }
+
+        to_despawn.push(entity);
+        if let Some(entity_children) = children.get(&entity) {
+            stack.extend(entity_children);
+        }
+    }
+
+    for entity in to_despawn {
+        // A hook may already have despawned it
+        let _ = ctx.despawn(entity);
+    }
+}
@@ -0,0 +1,236 @@
+use serde::{Deserialize, Serialize};
+
+use crate::scene::scene_loader::ComponentLoader;
+use crate::core::error::EngineError;
+use crate::scene::scene_loader::{insert_component, parse_component, save_component};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TimerMode {
+    #[default]
+    Once,
+    Repeating
+}
+
+/// Counts down `duration` seconds and sends a `TimerFinished` event carrying `tag` when done.
+/// A `Once` timer stays finished until `reset`, a `Repeating` one starts over.
+#[derive(Debug, Clone)]
+pub struct Timer {
+    pub tag: String,
+    pub duration: f32,
+    pub mode: TimerMode,
+    pub paused: bool,
+    /// Ticks with the real frame time, ignoring `Time::time_scale` and pause.
+    pub unscaled: bool,
+    elapsed: f32,
+    finished: bool,
+    times_finished_this_tick: u32
+}
+
+impl Timer {
+    pub fn new(tag: &str, duration: f32, mode: TimerMode) -> Self {
+        Self {
+            tag: tag.to_string(),
+            duration,
+            mode,
+            paused: false,
+            unscaled: false,
+            elapsed: 0.0,
+            finished: false,
+            times_finished_this_tick: 0
+        }
+    }
+
+    pub fn once(tag: &str, duration: f32) -> Self {
+        Self::new(tag, duration, TimerMode::Once)
+    }
+
+    pub fn repeating(tag: &str, duration: f32) -> Self {
+        Self::new(tag, duration, TimerMode::Repeating)
+    }
+
+    /// Advances the timer and returns how many times it completed during this tick.
+    pub fn tick(&mut self, dt: f32) -> u32 {
+        self.times_finished_this_tick = 0;
+
+        if self.paused || (self.finished && self.mode == TimerMode::Once) {
+            return 0;
+        }
+
+        self.elapsed += dt;
+
+        if self.elapsed >= self.duration {
+            match self.mode {
+                TimerMode::Once => {
+                    self.elapsed = self.duration;
+                    self.times_finished_this_tick = 1;
+                },
+                TimerMode::Repeating => {
+                    if self.duration > 0.0 {
+                        self.times_finished_this_tick = (self.elapsed / self.duration) as u32;
+                        self.elapsed %= self.duration;
+                    } else {
+                        self.times_finished_this_tick = 1;
+                        self.elapsed = 0.0;
+                    }
+                }
+            }
+            self.finished = true;
+        }
+
+        self.times_finished_this_tick
+    }
+
+    /// True once the timer has completed at least once.
+    pub fn finished(&self) -> bool {
+        self.finished
+    }
+
+    /// True if the timer completed during the last tick.
+    pub fn just_finished(&self) -> bool {
+        self.times_finished_this_tick > 0
+    }
+
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    pub fn remaining(&self) -> f32 {
+        (self.duration - self.elapsed).max(0.0)
+    }
+
+    /// Progress of the current cycle (0.0 - 1.0).
+    pub fn fraction(&self) -> f32 {
+        if self.duration > 0.0 {
+            (self.elapsed / self.duration).clamp(0.0, 1.0)
+        } else {
+            1.0
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.elapsed = 0.0;
+        self.finished = false;
+        self.times_finished_this_tick = 0;
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct TimerLoaderData {
+    #[serde(default)]
+    pub tag: String,
+    pub duration: f32,
+
+    #[serde(default)]
+    pub mode: TimerMode,
+
+    #[serde(default)]
+    pub paused: bool,
+
+    #[serde(default)]
+    pub unscaled: bool
+}
+
+pub struct TimerLoader;
+
+impl ComponentLoader for TimerLoader {
+    fn load(&self, ctx: &mut crate::prelude::Context, entity: hecs::Entity, data: &serde_json::Value) -> Result<(), EngineError> {
+        let loader_data: TimerLoaderData = parse_component("Timer", data)?;
+
+        let mut component = Timer::new(&loader_data.tag, loader_data.duration, loader_data.mode);
+        component.paused = loader_data.paused;
+        component.unscaled = loader_data.unscaled;
+
+        insert_component(ctx, entity, component)
+    }
+
+    fn save(&self, ctx: &crate::prelude::Context, entity: hecs::Entity) -> Option<serde_json::Value> {
+        save_component(ctx, entity, |component: &Timer| TimerLoaderData {
+            tag: component.tag.clone(),
+            duration: component.duration,
+            mode: component.mode,
+            paused: component.paused,
+            unscaled: component.unscaled
+        })
+    }
+}
+
+/// Gates an action so it can only happen once every `duration` seconds.
+#[derive(Debug, Clone, Copy)]
+pub struct Cooldown {
+    pub duration: f32,
+    /// Ticks with the real frame time, ignoring `Time::time_scale` and pause.
+    pub unscaled: bool,
+    remaining: f32
+}
+
+impl Cooldown {
+    /// Starts ready to be triggered.
+    pub fn new(duration: f32) -> Self {
+        Self {
+            duration,
+            unscaled: false,
+            remaining: 0.0
+        }
+    }
+
+    pub fn tick(&mut self, dt: f32) {
+        self.remaining = (self.remaining - dt).max(0.0);
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.remaining <= 0.0
+    }
+
+    /// Starts the cooldown if it is ready. Returns whether the action may happen.
+    pub fn trigger(&mut self) -> bool {
+        if !self.is_ready() {
+            return false;
+        }
+
+        self.remaining = self.duration;
+        true
+    }
+
+    pub fn remaining(&self) -> f32 {
+        self.remaining
+    }
+
+    pub fn reset(&mut self) {
+        self.remaining = 0.0;
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct CooldownLoaderData {
+    pub duration: f32,
+
+    /// Seconds left before the first trigger, 0 means ready.
+    #[serde(default)]
+    pub remaining: f32,
+
+    #[serde(default)]
+    pub unscaled: bool
+}
+
+pub struct CooldownLoader;
+
+impl ComponentLoader for CooldownLoader {
+    fn load(&self, ctx: &mut crate::prelude::Context, entity: hecs::Entity, data: &serde_json::Value) -> Result<(), EngineError> {
+        let loader_data: CooldownLoaderData = parse_component("Cooldown", data)?;
+
+        let mut component = Cooldown::new(loader_data.duration);
+        component.remaining = loader_data.remaining.max(0.0);
+        component.unscaled = loader_data.unscaled;
+
+        insert_component(ctx, entity, component)
+    }
+
+    fn save(&self, ctx: &crate::prelude::Context, entity: hecs::Entity) -> Option<serde_json::Value> {
+        save_component(ctx, entity, |component: &Cooldown| CooldownLoaderData {
+            duration: component.duration,
+            remaining: component.remaining,
+            unscaled: component.unscaled
+        })
+    }
+}
@@ -0,0 +1,8 @@
+use hecs::Entity;
+
+/// Sent by `timer_tick_system` each time a `Timer` completes.
+#[derive(Debug, Clone)]
+pub struct TimerFinished {
+    pub entity: Entity,
+    pub tag: String
+}
@@ -0,0 +1,4 @@
+pub mod components;
+pub mod systems;
+pub mod plugins;
+pub mod event;
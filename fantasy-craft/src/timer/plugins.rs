@@ -0,0 +1,21 @@
+use crate::prelude::{App, Plugin, Stage, System};
+use crate::timer::components::{CooldownLoader, TimerLoader};
+use crate::timer::systems::timer_tick_system;
+use crate::core::plugins::{CorePlugin, PluginId};
+
+pub struct TimerPlugin;
+
+impl Plugin for TimerPlugin {
+    fn build(&self, app: &mut App) {
+        app.scene_loader
+            .register("Timer", Box::new(TimerLoader))
+            .register("Cooldown", Box::new(CooldownLoader));
+
+        // Timers keep ticking in every state, pausing goes through `Time::paused`
+        app.add_system(Stage::Update, System::always(timer_tick_system).label("timer_tick"));
+    }
+
+    fn dependencies(&self) -> Vec<PluginId> {
+        vec![PluginId::of::<CorePlugin>()]
+    }
+}
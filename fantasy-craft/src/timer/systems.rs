@@ -0,0 +1,133 @@
+use crate::core::event::EventBus;
+use crate::prelude::Context;
+use crate::timer::components::{Cooldown, Timer};
+use crate::timer::event::TimerFinished;
+
+/// Ticks every `Timer` and `Cooldown`, then sends one `TimerFinished` per completion.
+pub fn timer_tick_system(ctx: &mut Context) {
+    let dt = ctx.dt();
+    let unscaled_dt = ctx.unscaled_dt();
+
+    let mut finished = Vec::new();
+
+    for (entity, timer) in ctx.world.query::<&mut Timer>().iter() {
+        let timer_dt = if timer.unscaled { unscaled_dt } else { dt };
+
+        for _ in 0..timer.tick(timer_dt) {
+            finished.push(TimerFinished {
+                entity,
+                tag: timer.tag.clone()
+            });
+        }
+    }
+
+    for (_, cooldown) in ctx.world.query::<&mut Cooldown>().iter() {
+        cooldown.tick(if cooldown.unscaled { unscaled_dt } else { dt });
+    }
+
+    if finished.is_empty() {
+        return;
+    }
+
+    if let Some(event_bus) = ctx.get_resource_mut::<EventBus>() {
+        for event in finished {
+            event_bus.send(event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hecs::World;
+
+    use super::*;
+    use crate::core::event::EventReader;
+    use crate::core::time::{DeltaTime, Time};
+    use crate::prelude::AssetServer;
+    use crate::timer::components::TimerMode;
+
+    #[test]
+    fn once_timer_finishes_a_single_time() {
+        let mut timer = Timer::once("door", 1.0);
+
+        assert_eq!(timer.tick(0.6), 0);
+        assert!(!timer.finished());
+        assert_eq!(timer.tick(0.6), 1);
+        assert!(timer.just_finished());
+        assert_eq!(timer.elapsed(), 1.0);
+
+        assert_eq!(timer.tick(5.0), 0);
+        assert!(timer.finished());
+        assert!(!timer.just_finished());
+
+        timer.reset();
+        assert_eq!(timer.tick(1.0), 1);
+    }
+
+    #[test]
+    fn repeating_timer_counts_every_completion() {
+        let mut timer = Timer::repeating("spawn", 0.5);
+
+        assert_eq!(timer.tick(0.25), 0);
+        assert_eq!(timer.tick(1.0), 2);
+        assert!((timer.elapsed() - 0.25).abs() < 1e-5);
+        assert_eq!(timer.tick(0.25), 1);
+        assert_eq!(timer.mode, TimerMode::Repeating);
+    }
+
+    #[test]
+    fn paused_timer_does_not_advance() {
+        let mut timer = Timer::once("door", 1.0);
+        timer.paused = true;
+
+        assert_eq!(timer.tick(2.0), 0);
+        assert_eq!(timer.elapsed(), 0.0);
+    }
+
+    #[test]
+    fn unscaled_timers_ignore_time_scale_and_pause() {
+        let mut ctx = Context::new(World::new(), AssetServer::new());
+        let mut time = Time::new();
+        time.paused = true;
+        time.advance(1.0);
+        ctx.insert_resource(DeltaTime(time.delta()));
+        ctx.insert_resource(time);
+        ctx.insert_resource(EventBus::new());
+
+        let scaled = ctx.spawn((Timer::once("scaled", 0.5), Cooldown::new(0.5)));
+        let mut unscaled_timer = Timer::once("unscaled", 0.5);
+        unscaled_timer.unscaled = true;
+        let mut unscaled_cooldown = Cooldown::new(0.5);
+        unscaled_cooldown.unscaled = true;
+        let unscaled = ctx.spawn((unscaled_timer, unscaled_cooldown));
+
+        for entity in [scaled, unscaled] {
+            assert!(ctx.world.get::<&mut Cooldown>(entity).unwrap().trigger());
+        }
+
+        timer_tick_system(&mut ctx);
+
+        let tags: Vec<String> = EventReader::<TimerFinished>::new()
+            .read(ctx.resource::<EventBus>())
+            .map(|event| event.tag.clone())
+            .collect();
+        assert_eq!(tags, ["unscaled"]);
+
+        assert!(!ctx.world.get::<&Timer>(scaled).unwrap().finished());
+        assert!(!ctx.world.get::<&Cooldown>(scaled).unwrap().is_ready());
+        assert!(ctx.world.get::<&Cooldown>(unscaled).unwrap().is_ready());
+    }
+
+    #[test]
+    fn cooldown_gates_triggers() {
+        let mut cooldown = Cooldown::new(1.0);
+
+        assert!(cooldown.trigger());
+        assert!(!cooldown.trigger());
+
+        cooldown.tick(0.75);
+        assert!(!cooldown.is_ready());
+        cooldown.tick(0.75);
+        assert!(cooldown.trigger());
+    }
+}
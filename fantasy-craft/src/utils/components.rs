@@ -1,4 +1,6 @@
 use crate::scene::scene_loader::ComponentLoader;
+use crate::core::error::EngineError;
+use crate::scene::scene_loader::{insert_component, parse_component, save_component};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Direction {
@@ -58,13 +60,16 @@ pub struct DirectionComponent(pub Direction);
 pub struct DirectionComponentLoader;
 
 impl ComponentLoader for DirectionComponentLoader {
-    fn load(&self, ctx: &mut crate::prelude::Context, entity: hecs::Entity, data: &serde_json::Value) {
-        let loader_data: String = serde_json::from_value(data.clone())
-            .unwrap_or_default();
+    fn load(&self, ctx: &mut crate::prelude::Context, entity: hecs::Entity, data: &serde_json::Value) -> Result<(), EngineError> {
+        let loader_data: String = parse_component("DirectionComponent", data)?;
 
         let component = DirectionComponent(Direction::from_str(&loader_data));
 
-        ctx.world.insert_one(entity, component).expect("Failed to insert DirectionComponent");
+        insert_component(ctx, entity, component)
+    }
+
+    fn save(&self, ctx: &crate::prelude::Context, entity: hecs::Entity) -> Option<serde_json::Value> {
+        save_component(ctx, entity, |component: &DirectionComponent| component.0.to_str())
     }
 }
 
@@ -74,44 +79,53 @@ pub struct StateComponent(pub State);
 pub struct StateComponentLoader;
 
 impl ComponentLoader for StateComponentLoader {
-    fn load(&self, ctx: &mut crate::prelude::Context, entity: hecs::Entity, data: &serde_json::Value) {
-        let loader_data: String = serde_json::from_value(data.clone())
-            .unwrap_or_default();
+    fn load(&self, ctx: &mut crate::prelude::Context, entity: hecs::Entity, data: &serde_json::Value) -> Result<(), EngineError> {
+        let loader_data: String = parse_component("StateComponent", data)?;
 
         let component = StateComponent(State::from_str(&loader_data));
 
-        ctx.world.insert_one(entity, component).expect("Failed to insert StateComponent");
+        insert_component(ctx, entity, component)
+    }
+
+    fn save(&self, ctx: &crate::prelude::Context, entity: hecs::Entity) -> Option<serde_json::Value> {
+        save_component(ctx, entity, |component: &StateComponent| component.0.to_str())
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Visible(pub bool);
 
 pub struct VisibleLoader;
 
 impl ComponentLoader for VisibleLoader {
-    fn load(&self, ctx: &mut crate::prelude::Context, entity: hecs::Entity, data: &serde_json::Value) {
-        let loader_data: bool = serde_json::from_value(data.clone())
-            .unwrap_or_default();
+    fn load(&self, ctx: &mut crate::prelude::Context, entity: hecs::Entity, data: &serde_json::Value) -> Result<(), EngineError> {
+        let loader_data: bool = parse_component("Visible", data)?;
 
         let component = Visible(loader_data);
 
-        ctx.world.insert_one(entity, component).expect("Failed to insert Visible");
+        insert_component(ctx, entity, component)
+    }
+
+    fn save(&self, ctx: &crate::prelude::Context, entity: hecs::Entity) -> Option<serde_json::Value> {
+        save_component(ctx, entity, |component: &Visible| component.0)
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct LocalVisible(pub bool);
 
 pub struct LocalVisibleLoader;
 
 impl ComponentLoader for LocalVisibleLoader {
-    fn load(&self, ctx: &mut crate::prelude::Context, entity: hecs::Entity, data: &serde_json::Value) {
-        let loader_data: bool = serde_json::from_value(data.clone())
-            .unwrap_or_default();
+    fn load(&self, ctx: &mut crate::prelude::Context, entity: hecs::Entity, data: &serde_json::Value) -> Result<(), EngineError> {
+        let loader_data: bool = parse_component("LocalVisible", data)?;
 
         let component = LocalVisible(loader_data);
 
-        ctx.world.insert_one(entity, component).expect("Failed to insert LocalVisible");
+        insert_component(ctx, entity, component)
+    }
+
+    fn save(&self, ctx: &crate::prelude::Context, entity: hecs::Entity) -> Option<serde_json::Value> {
+        save_component(ctx, entity, |component: &LocalVisible| component.0)
     }
 }
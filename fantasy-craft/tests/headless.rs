@@ -0,0 +1,72 @@
+use fantasy_craft::core::event::{EventBus, EventReader};
+use fantasy_craft::core::time::Time;
+use fantasy_craft::prelude::*;
+use macroquad::prelude::*;
+
+fn headless_app() -> App {
+    let mut app = App::new(Conf::default());
+    app.with_seed(42).add_plugins(Default2dPlugin);
+    app
+}
+
+fn spawn_body(app: &mut App, position: Vec2, body_type: BodyType) -> hecs::Entity {
+    app.context.spawn((
+        Transform { position, ..Default::default() },
+        RigidBody::new(body_type),
+        Collider::new_box(16.0, 16.0),
+        Velocity(Vec2::ZERO)
+    ))
+}
+
+#[test]
+fn default_plugins_step_without_a_window() {
+    let mut app = headless_app();
+
+    app.step(120);
+
+    assert!(app.context.get_resource::<Headless>().is_some());
+    assert!(app.context.resource::<Time>().elapsed() > 1.9);
+}
+
+#[test]
+fn dynamic_body_moves_and_stops_against_a_wall() {
+    let mut app = headless_app();
+    let body = spawn_body(&mut app, vec2(0.0, 0.0), BodyType::Dynamic);
+    let wall = spawn_body(&mut app, vec2(40.0, 0.0), BodyType::Static);
+    app.context.world.get::<&mut Velocity>(body).unwrap().0 = vec2(60.0, 0.0);
+
+    let mut collisions = EventReader::<CollisionEvent>::new();
+    let mut collided = false;
+
+    for _ in 0..120 {
+        app.step(1);
+        collided |= collisions.read(app.context.resource::<EventBus>())
+            .any(|event| (event.entity_a, event.entity_b) == (body, wall) || (event.entity_a, event.entity_b) == (wall, body));
+    }
+
+    let body_x = app.context.world.get::<&Transform>(body).unwrap().position.x;
+    let wall_x = app.context.world.get::<&Transform>(wall).unwrap().position.x;
+
+    assert!(collided);
+    assert!(body_x > 20.0, "the body did not move: {}", body_x);
+    assert!(body_x <= wall_x - 16.0 + 0.01, "the body went through the wall: {}", body_x);
+    assert_eq!(wall_x, 40.0);
+}
+
+#[test]
+fn state_transition_stages_are_profiled_in_their_frame() {
+    let mut app = headless_app();
+    app.add_plugin(ProfilerPlugin)
+        .add_system(Stage::Update, System::new(|ctx: &mut Context| ctx.request_state(GameState::Paused), vec![GameState::Playing]))
+        .add_system(Stage::on_enter(GameState::Paused), System::new(|_: &mut Context| {}, vec![GameState::Paused]));
+    app.build_plugins();
+    app.context.resource_mut::<Profiler>().enabled = true;
+
+    app.step(1);
+
+    let frame = app.context.resource::<Profiler>().last_frame().unwrap();
+    let on_enter = frame.stages.iter()
+        .find(|stage| stage.stage == "OnEnter(GameState::Paused)")
+        .expect("OnEnter(Paused) was not recorded in the frame of the transition");
+    assert!(on_enter.start >= frame.start && on_enter.start + on_enter.duration <= frame.start + frame.duration);
+}
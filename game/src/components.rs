@@ -1,6 +1,6 @@
-use fantasy_craft::prelude::{ComponentLoader, Context};
+use fantasy_craft::prelude::{ComponentLoader, Context, EngineError, insert_component, parse_component, save_component, save_marker};
 use macroquad::prelude::*;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Behavior {
@@ -8,7 +8,6 @@ pub enum Behavior {
     Wander
 }
 
-#[allow(dead_code)]
 impl Behavior {
     pub fn to_str(&self) -> &'static str {
         match self {
@@ -32,13 +31,16 @@ pub struct BehaviorComponent(pub Behavior);
 pub struct BehaviorComponentLoader;
 
 impl ComponentLoader for BehaviorComponentLoader {
-    fn load(&self, ctx: &mut Context, entity: hecs::Entity, data: &serde_json::Value) {
-        let loader_data: String = serde_json::from_value(data.clone())
-            .unwrap_or_default();
+    fn load(&self, ctx: &mut Context, entity: hecs::Entity, data: &serde_json::Value) -> Result<(), EngineError> {
+        let loader_data: String = parse_component("BehaviorComponent", data)?;
 
         let component = BehaviorComponent(Behavior::from_str(&loader_data));
 
-        ctx.world.insert_one(entity, component).expect("Failed to insert BehaviorComponent")
+        insert_component(ctx, entity, component)
+    }
+
+    fn save(&self, ctx: &Context, entity: hecs::Entity) -> Option<serde_json::Value> {
+        save_component(ctx, entity, |component: &BehaviorComponent| component.0.to_str())
     }
 }
 
@@ -48,8 +50,12 @@ pub struct PlayerTag;
 pub struct PlayerTagLoader;
 
 impl ComponentLoader for PlayerTagLoader {
-    fn load(&self, ctx: &mut Context, entity: hecs::Entity, _data: &serde_json::Value) {
-        ctx.world.insert_one(entity, PlayerTag).expect("Failed to insert PlayerTag");
+    fn load(&self, ctx: &mut Context, entity: hecs::Entity, _data: &serde_json::Value) -> Result<(), EngineError> {
+        insert_component(ctx, entity, PlayerTag)
+    }
+
+    fn save(&self, ctx: &Context, entity: hecs::Entity) -> Option<serde_json::Value> {
+        save_marker::<PlayerTag>(ctx, entity)
     }
 }
 
@@ -59,13 +65,16 @@ pub struct AnimationPrefix(pub String);
 pub struct AnimationPrefixLoader;
 
 impl ComponentLoader for AnimationPrefixLoader {
-    fn load(&self, ctx: &mut Context, entity: hecs::Entity, data: &serde_json::Value) {
-        let loader_data: String = serde_json::from_value(data.clone())
-            .unwrap_or_default();
+    fn load(&self, ctx: &mut Context, entity: hecs::Entity, data: &serde_json::Value) -> Result<(), EngineError> {
+        let loader_data: String = parse_component("AnimationPrefix", data)?;
 
         let component = AnimationPrefix(loader_data);
 
-        ctx.world.insert_one(entity, component).expect("Failed to insert AnimationPrefix");
+        insert_component(ctx, entity, component)
+    }
+
+    fn save(&self, ctx: &Context, entity: hecs::Entity) -> Option<serde_json::Value> {
+        save_component(ctx, entity, |component: &AnimationPrefix| component.0.clone())
     }
 }
 
@@ -76,7 +85,7 @@ pub struct NpcTag {
     pub wander_target_duration: f32
 }
 
-#[derive(Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct NpcTagLoaderData {
     pub name: String,
     pub wander_time: f32,
@@ -86,9 +95,8 @@ pub struct NpcTagLoaderData {
 pub struct NpcTagLoader;
 
 impl ComponentLoader for NpcTagLoader {
-    fn load(&self, ctx: &mut Context, entity: hecs::Entity, data: &serde_json::Value) {
-        let loader_data: NpcTagLoaderData = serde_json::from_value(data.clone())
-            .unwrap_or_default();
+    fn load(&self, ctx: &mut Context, entity: hecs::Entity, data: &serde_json::Value) -> Result<(), EngineError> {
+        let loader_data: NpcTagLoaderData = parse_component("NpcTag", data)?;
 
         let component = NpcTag {
             name: loader_data.name,
@@ -96,35 +104,45 @@ impl ComponentLoader for NpcTagLoader {
             wander_target_duration: loader_data.wander_target_duration
         };
 
-        ctx.world.insert_one(entity, component).expect("Failed to insert NpcTag");
+        insert_component(ctx, entity, component)
+    }
+
+    fn save(&self, ctx: &Context, entity: hecs::Entity) -> Option<serde_json::Value> {
+        save_component(ctx, entity, |component: &NpcTag| NpcTagLoaderData {
+            name: component.name.clone(),
+            wander_time: component.wander_time,
+            wander_target_duration: component.wander_target_duration
+        })
     }
 }
 
 #[derive(Debug)]
 pub struct FpsDisplay {
-    pub fps_timer: f32,
     pub displayed_fps: i32
 }
 
-#[derive(Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct FpsDisplayLoaderData {
-    pub fps_timer: f32,
     pub displayed_fps: i32
 }
 
 pub struct FpsDisplayLoader;
 
 impl ComponentLoader for FpsDisplayLoader {
-    fn load(&self, ctx: &mut Context, entity: hecs::Entity, data: &serde_json::Value) {
-        let loader_data: FpsDisplayLoaderData = serde_json::from_value(data.clone())
-            .unwrap_or_default();
+    fn load(&self, ctx: &mut Context, entity: hecs::Entity, data: &serde_json::Value) -> Result<(), EngineError> {
+        let loader_data: FpsDisplayLoaderData = parse_component("FpsDisplay", data)?;
 
         let component = FpsDisplay {
-            fps_timer: loader_data.fps_timer,
             displayed_fps: loader_data.displayed_fps
         };
 
-        ctx.world.insert_one(entity, component).expect("Failed to insert FpsDisplay");
+        insert_component(ctx, entity, component)
+    }
+
+    fn save(&self, ctx: &Context, entity: hecs::Entity) -> Option<serde_json::Value> {
+        save_component(ctx, entity, |component: &FpsDisplay| FpsDisplayLoaderData {
+            displayed_fps: component.displayed_fps
+        })
     }
 }
 
@@ -134,7 +152,11 @@ pub struct MainMenu;
 pub struct MainMenuLoader;
 
 impl ComponentLoader for MainMenuLoader {
-    fn load(&self, ctx: &mut Context, entity: hecs::Entity, _data: &serde_json::Value) {
-        ctx.world.insert_one(entity, MainMenu).expect("Failed to insert MainMenu");
+    fn load(&self, ctx: &mut Context, entity: hecs::Entity, _data: &serde_json::Value) -> Result<(), EngineError> {
+        insert_component(ctx, entity, MainMenu)
+    }
+
+    fn save(&self, ctx: &Context, entity: hecs::Entity) -> Option<serde_json::Value> {
+        save_marker::<MainMenu>(ctx, entity)
     }
 }
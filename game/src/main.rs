@@ -26,7 +26,10 @@ use crate::components::FpsDisplayLoader;
 use crate::plugins::{NpcPlugin, PlayerPlugin};
 use crate::systems::fps_display_update;
 
-fn window_conf() -> Conf {
+const PROJECT_FILE: &str = "resources/project.json";
+
+// Used when the project file is missing or leaves a value out
+fn default_conf() -> Conf {
     Conf {
         window_title: "Fantasy Craft".to_owned(),
         window_width: 1920,
@@ -35,26 +38,29 @@ fn window_conf() -> Conf {
     }
 }
 
+fn window_conf() -> Conf {
+    ProjectSettings::window_conf(PROJECT_FILE, default_conf(), WindowSettings::default())
+}
+
 #[macroquad::main(window_conf)]
 async fn main() {
-    let mut app = App::new(window_conf());
+    // The window already exists, the `App` reads its real size at startup
+    let mut app = App::new(default_conf());
 
     app.scene_loader
         .register("FpsDisplay", Box::new(FpsDisplayLoader));
 
     app
-        .with_splash_screen_enabled(true)
-        .with_assets_file("resources/assets.json".to_string())
-        .with_scene_path("resources/scenes/dev.json".to_string())
-        .with_binding_file("resources/bindings.json".to_string())
-        .add_plugin(Default2dPlugin)
+        .with_project_file(PROJECT_FILE.to_string())
+        .add_plugins(Default2dPlugin)
         .add_plugin(DebugPlugin)
+        .add_plugin(ProfilerPlugin)
         .add_plugin(PlayerPlugin)
         .add_plugin(NpcPlugin)
-        .add_system(Stage::Update, System::new(
+        .add_system(Stage::Update, System::with_local(
             fps_display_update,
             vec![GameState::Playing, GameState::Menu]
-        ));
+        ).run_if(has_window()));
 
     app.run().await
 }
@@ -1,6 +1,6 @@
-use fantasy_craft::prelude::*;
+use fantasy_craft::{audio::plugin::AudioPlugin, input::plugin::InputPlugin, prelude::*};
 
-use crate::{components::{AnimationPrefixLoader, BehaviorComponentLoader, MainMenuLoader, NpcTagLoader, PlayerTagLoader}, systems::{check_player_npc_collision, npc_behavior_system, player_update, menu_buttons_system, toggle_main_menu_system}};
+use crate::{components::{AnimationPrefixLoader, BehaviorComponentLoader, MainMenuLoader, NpcTagLoader, PlayerTagLoader}, systems::{check_player_npc_collision, hide_main_menu, menu_buttons_system, npc_behavior_system, player_update, show_main_menu, toggle_main_menu_system}};
 
 pub struct PlayerPlugin;
 
@@ -17,20 +17,32 @@ impl Plugin for PlayerPlugin {
             .add_system(Stage::Update, System::new(
                 player_update,
                 vec![GameState::Playing]
-            ))
-            .add_system(Stage::Update, System::new(
+            ).run_if(has_window()))
+            .add_system(Stage::Update, System::with_local(
                 menu_buttons_system,
                 vec![GameState::Menu]
             ))
             .add_system(Stage::Update, System::new(
                 toggle_main_menu_system,
                 vec![GameState::Playing, GameState::Menu]
+            ).run_if(action_just_pressed("Menu")))
+            .add_system(Stage::on_enter(GameState::Menu), System::new(
+                show_main_menu,
+                vec![GameState::Menu]
             ))
-            .add_system(Stage::PostUpdate, System::new(
+            .add_system(Stage::on_exit(GameState::Menu), System::new(
+                hide_main_menu,
+                vec![GameState::Menu]
+            ))
+            .add_system(Stage::PostUpdate, System::with_local(
                 check_player_npc_collision,
                 vec![GameState::Playing]
             ));
     }
+
+    fn dependencies(&self) -> Vec<PluginId> {
+        vec![PluginId::of::<GuiPlugin>(), PluginId::of::<InputPlugin>(), PluginId::of::<PhysicsPlugin>(), PluginId::of::<AudioPlugin>()]
+    }
 }
 
 pub struct NpcPlugin;
@@ -38,9 +50,71 @@ pub struct NpcPlugin;
 impl Plugin for NpcPlugin {
     fn build(&self, app: &mut App) {
         app
-            .add_system(Stage::Update, System::new(
+            .add_system(Stage::FixedUpdate, System::new(
                 npc_behavior_system,
                 vec![GameState::Playing]
-            ));
+            ).label("npc_behavior").after("store_previous_positions").before("physics"));
+    }
+
+    fn dependencies(&self) -> Vec<PluginId> {
+        vec![PluginId::of::<PhysicsPlugin>()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use macroquad::prelude::*;
+
+    use super::*;
+    use crate::components::{Behavior, BehaviorComponent, NpcTag};
+
+    fn spawn_npc(app: &mut App, behavior: Behavior) -> hecs::Entity {
+        app.context.spawn((
+            Transform { position: vec2(100.0, 100.0), ..Default::default() },
+            NpcTag {
+                name: "farmer".to_string(),
+                wander_time: 0.0,
+                wander_target_duration: 0.5
+            },
+            BehaviorComponent(behavior),
+            StateComponent(State::Idle),
+            DirectionComponent(Direction::Down),
+            Speed(40.0),
+            AnimationComponent(String::new()),
+            RigidBody::new(BodyType::Dynamic),
+            Collider::new_box(16.0, 16.0),
+            Velocity(Vec2::ZERO)
+        ))
+    }
+
+    fn wander(seed: u64, frames: u32) -> Vec2 {
+        let mut app = App::new(Conf::default());
+        app.with_seed(seed)
+            .add_plugins(Default2dPlugin)
+            .add_plugin(NpcPlugin);
+
+        let npc = spawn_npc(&mut app, Behavior::Wander);
+        app.step(frames);
+
+        app.context.world.get::<&Transform>(npc).unwrap().position
+    }
+
+    #[test]
+    fn wandering_npc_moves_the_same_way_for_a_seed() {
+        let position = wander(7, 600);
+
+        assert_ne!(position, vec2(100.0, 100.0));
+        assert_eq!(position, wander(7, 600));
+    }
+
+    #[test]
+    fn standing_npc_stays_in_place() {
+        let mut app = App::new(Conf::default());
+        app.add_plugins(Default2dPlugin).add_plugin(NpcPlugin);
+
+        let npc = spawn_npc(&mut app, Behavior::Stand);
+        app.step(120);
+
+        assert_eq!(app.context.world.get::<&Transform>(npc).unwrap().position, vec2(100.0, 100.0));
     }
 }
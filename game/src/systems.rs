@@ -1,24 +1,27 @@
-use std::process::exit;
-
 use hecs::Entity;
 use macroquad::prelude::*;
 // removed: use macroquad::rand::*; // 'prelude' already includes rand, but explicit use is fine too if you prefer.
 // removed: use ::rand::{seq::IteratorRandom, thread_rng, Rng}; // We don't need the external rand crate anymore.
 
-use fantasy_craft::{audio::event::PlaySoundEvent, core::event::EventBus, gui::{event::UiClickEvent}, input::{focus::InputFocus, manager::InputManager}, prelude::*};
+use fantasy_craft::{audio::event::PlaySoundEvent, core::event::{EventBus, EventReader}, gui::{event::UiClickEvent}, input::{focus::InputFocus, manager::InputManager}, prelude::*};
 use crate::components::{AnimationPrefix, Behavior, BehaviorComponent, FpsDisplay, MainMenu, NpcTag, PlayerTag};
 use fantasy_craft::gui::text_display::TextDisplay;
 
-/// System handling NPC logic using Macroquad's RNG
+/// System handling NPC logic, drawing from the "npc" stream of the engine's seeded Rng
 pub fn npc_behavior_system(ctx: &mut Context) {
+    let dt = ctx.dt();
+    let (world, resources) = (&mut ctx.world, &mut ctx.resources);
+    let rng = resources.get_mut::<Rng>().expect("Rng resource missing").stream("npc");
+    let mut moved = Vec::new();
+
     // We iterate over all entities with the required components
-    for (_, (transform, npc, behavior, state, direction, speed, animation_comp)) in ctx.world.query::<(&mut Transform, &mut NpcTag, &BehaviorComponent, &mut StateComponent, &mut DirectionComponent, &Speed, &mut AnimationComponent)>().iter() {
+    for (entity, (transform, npc, behavior, state, direction, speed, animation_comp)) in world.query::<(&mut Transform, &mut NpcTag, &BehaviorComponent, &mut StateComponent, &mut DirectionComponent, &Speed, &mut AnimationComponent)>().iter() {
         match behavior.0 {
             Behavior::Stand => {
                 state.0 = State::Idle;
             },
             Behavior::Wander => {
-                npc.wander_time += ctx.dt();
+                npc.wander_time += dt;
                 
                 // Logic when the wander timer exceeds the target duration
                 if npc.wander_time >= npc.wander_target_duration {
@@ -30,21 +33,15 @@ pub fn npc_behavior_system(ctx: &mut Context) {
                         // Define available directions
                         let directions = [Direction::Up, Direction::Down, Direction::Left, Direction::Right];
                         
-                        // MACROQUAD CHANGE: 
-                        // Instead of using iterator.choose(), we generate a random index.
-                        // gen_range(min, max) with integers excludes the max, so 0..len is correct.
-                        let random_index = rand::gen_range(0, directions.len());
-                        direction.0 = directions[random_index];
-
-                        // MACROQUAD CHANGE:
-                        // Replaced rng.gen_range(1.0..=3.0) with rand::gen_range(min, max)
-                        npc.wander_target_duration = rand::gen_range(1.0, 3.0);
+                        if let Some(random_direction) = rng.choose(&directions) {
+                            direction.0 = *random_direction;
+                        }
+
+                        npc.wander_target_duration = rng.range_f32(1.0, 3.0);
                     }
                     else {
                         state.0 = State::Idle;
-                        // MACROQUAD CHANGE:
-                        // Replaced rng.gen_range(2.0..=5.0) with rand::gen_range(min, max)
-                        npc.wander_target_duration = rand::gen_range(2.0, 5.0);
+                        npc.wander_target_duration = rng.range_f32(2.0, 5.0);
                     }
                 }
 
@@ -56,7 +53,8 @@ pub fn npc_behavior_system(ctx: &mut Context) {
                         Direction::Left => vec2(-1.0, 0.0),
                         Direction::Right => vec2(1.0, 0.0)
                     };
-                    transform.position += direction_vec * speed.0 * ctx.dt();
+                    transform.position += direction_vec * speed.0 * dt;
+                    moved.push(entity);
                 }
             }
         }
@@ -64,6 +62,10 @@ pub fn npc_behavior_system(ctx: &mut Context) {
         // Update animation string key
         animation_comp.0 = format!("{}_{}_{}", npc.name, state.0.to_str(), direction.0.to_str());
     }
+
+    for entity in moved {
+        ctx.mark_changed::<Transform>(entity);
+    }
 }
 
 pub fn player_update(ctx: &mut Context) {
@@ -139,24 +141,30 @@ pub fn player_update(ctx: &mut Context) {
     }
 }
 
-pub fn fps_display_update(ctx: &mut Context) {
-    for (_, (fps_display, text_display)) in ctx.world.query::<(&mut FpsDisplay, &mut TextDisplay)>().iter() {
-        fps_display.fps_timer += ctx.dt();
+pub fn fps_display_update(ctx: &mut Context, reader: &mut Local<EventReader<TimerFinished>>) {
+    // Each FPS display carries a repeating "fps_refresh" Timer
+    let (world, resources) = (&mut ctx.world, &ctx.resources);
+    let event_bus = resources.get::<EventBus>()
+        .expect("EventBus resource is missing");
 
-        if fps_display.fps_timer >= 1.0 {
+    for event in reader.read(event_bus) {
+        if event.tag != "fps_refresh" {
+            continue;
+        }
+
+        if let Ok((fps_display, text_display)) = world.query_one_mut::<(&mut FpsDisplay, &mut TextDisplay)>(event.entity) {
             fps_display.displayed_fps = get_fps();
-            fps_display.fps_timer = 0.0;
             text_display.text = format!("FPS: {}", fps_display.displayed_fps);
         }
     }
 }
 
-pub fn check_player_npc_collision(ctx: &mut Context) {
+pub fn check_player_npc_collision(ctx: &mut Context, reader: &mut Local<EventReader<CollisionEvent>>) {
     // 1. Access the EventBus resource
     let event_bus = ctx.resource::<EventBus>();
 
-    // 2. Read specific events using the generic read method.
-    for event in event_bus.read::<CollisionEvent>() {
+    // 2. Read the collisions sent since the last run.
+    for event in reader.read(event_bus) {
         let e_a = event.entity_a;
         let e_b = event.entity_b;
 
@@ -176,7 +184,7 @@ pub fn check_player_npc_collision(ctx: &mut Context) {
     }
 }
 
-pub fn menu_buttons_system(ctx: &mut Context) {
+pub fn menu_buttons_system(ctx: &mut Context, reader: &mut Local<EventReader<UiClickEvent>>) {
     let mut should_quit = false;
     let mut sound_to_play: Option<String> = None;
 
@@ -184,7 +192,7 @@ pub fn menu_buttons_system(ctx: &mut Context) {
     {
         let event_bus = ctx.resource::<EventBus>();
 
-        for event in event_bus.read::<UiClickEvent>() {
+        for event in reader.read(event_bus) {
             match event.action_id.as_str() {
                 "quit_game" => {
                     should_quit = true;
@@ -207,22 +215,37 @@ pub fn menu_buttons_system(ctx: &mut Context) {
 
     if should_quit {
         println!("Bye Fantasy Craft");
-        exit(0);
+        ctx.exit();
     }
 }
 
+/// Runs only on the frame the "Menu" action is pressed (see `PlayerPlugin`).
 pub fn toggle_main_menu_system(ctx: &mut Context) {
-    let input = ctx.resource::<InputManager>();
+    if ctx.state::<GameState>() == GameState::Menu {
+        ctx.pop_state::<GameState>();
+    } else {
+        ctx.push_state(GameState::Menu);
+    }
+}
 
-    if input.is_action_just_pressed("Menu") {
-        for (_, (_main_menu, visible)) in ctx.world.query::<(&MainMenu, &mut Visible)>().iter() {
-            visible.0 = !visible.0;
+fn set_main_menu_visible(ctx: &mut Context, is_visible: bool) {
+    let menus: Vec<Entity> = ctx.world.query::<(&MainMenu, &mut Visible)>()
+        .iter()
+        .map(|(entity, (_main_menu, visible))| {
+            visible.0 = is_visible;
+            entity
+        })
+        .collect();
 
-            ctx.game_state = if visible.0 {
-                GameState::Menu
-            } else {
-                GameState::Playing
-            };
-        }
+    for entity in menus {
+        ctx.mark_changed::<Visible>(entity);
     }
 }
+
+pub fn show_main_menu(ctx: &mut Context) {
+    set_main_menu_visible(ctx, true);
+}
+
+pub fn hide_main_menu(ctx: &mut Context) {
+    set_main_menu_visible(ctx, false);
+}